@@ -0,0 +1,5 @@
+l = [1, 2]
+try:
+    l[5]
+except IndexError:
+    assert(False)
@@ -0,0 +1,6 @@
+l = [1, 2]
+try:
+    l[5]
+    assert(False)
+except IndexError:
+    pass
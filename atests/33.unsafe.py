@@ -0,0 +1,4 @@
+try:
+    n = int(input())
+except ValueError:
+    assert(False)
@@ -0,0 +1,2 @@
+n = int(input())
+assert(type(n) == int)
@@ -0,0 +1,3 @@
+x = abs(-3)
+assert(x == 3)
+assert(isinstance(x, int))
@@ -0,0 +1,2 @@
+x = abs(-3)
+assert(x == -3)
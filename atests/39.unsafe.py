@@ -0,0 +1,3 @@
+d = {"a": 1, "b": 2}
+assert(d["a"] == 1)
+assert(len(d) == 3)
@@ -0,0 +1,8 @@
+def g():
+    yield 1
+    yield 2
+
+s = 0
+for x in g():
+    s += x
+assert(s == 4)
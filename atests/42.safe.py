@@ -0,0 +1,7 @@
+def f(a, b=2, c=3):
+    return a + b * c
+
+assert(f(1) == 7)
+assert(f(1, 4) == 13)
+assert(f(1, c=5) == 11)
+assert(f(c=1, a=2) == 4)
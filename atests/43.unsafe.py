@@ -0,0 +1,5 @@
+def f(a, b=2, c=3):
+    return a + b * c
+
+assert(f(1) == 7)
+assert(f(1, c=5) == 10)
@@ -0,0 +1,4 @@
+l = []
+for i in range(0, 3):
+    l.append("ok")
+assert(len(l) == 2)
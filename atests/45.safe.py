@@ -0,0 +1,14 @@
+def gen():
+    yield 2
+    yield 3
+
+s = sorted([3, 1, 2])
+assert(s[0] == 1 and s[1] == 2 and s[2] == 3)
+assert(max([3, 1, 2]) == 3)
+assert(min(4, 2, 5) == 2)
+assert(sum(gen()) == 5)
+
+d = {}
+d["k"] = 1
+del d["k"]
+assert(len(d) == 0)
@@ -0,0 +1,21 @@
+# operator precedence and associativity
+
+print(2 * 3 + 4)
+print(2 + 3 * 4)
+print(10 - 3 - 2)
+print(1 - 2 + 3)
+print(100 - 10 * 2 - 5)
+print(10 % 4 * 3)
+print(2 * 3 ** 2)
+print(2 ** 2 * 3)
+print(2 ** 3 ** 2)
+print(-2 ** 2)
+print(-3 * 2 + 1)
+print(2 - -3 * 4)
+print(-2 * -3)
+print(-(1 + 2) * 3)
+print(1 + 2 == 3)
+print(2 * 3 > 5)
+print(False and True or True)
+print(True or False and False)
+print(1 < 2 and 3 < 2 or 4 == 4)
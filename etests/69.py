@@ -0,0 +1,23 @@
+# line structure: implicit line joining, comment lines and one-line blocks
+
+l = [1,
+     2,
+  3,
+]
+print(l[0] + l[1] + l[2])
+
+def f(a,
+      b):
+    x = a
+# a comment that is less indented
+        # and one that is more indented
+    return x + b
+
+print(f(
+    4, 5))
+
+if f(1, 1) == 2: print("one-line if")
+else: print("one-line else")
+
+while False: pass
+print("done")   # trailing comment
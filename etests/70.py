@@ -0,0 +1,74 @@
+a, b = 1, 2
+a, b = b, a
+print(a)
+print(b)
+
+def divmod_(x, y):
+    return x - y, x % y
+
+q, r = divmod_(17, 5)
+print(q)
+print(r)
+
+for i, x in [(0, 10), (1, 20), (2, 30)]:
+    print(i + x)
+
+c = d = 0
+c = c + 1
+print(c)
+print(d)
+
+t = (1, 2, 3)
+print(t[0])
+print(t[-1])
+print(t[1:][0])
+print(t.__len__())
+print(t == (1, 2, 3))
+print(t == (1, 2))
+print(() == ())
+print((4,)[0])
+
+x, y, z = [7, 8, 9]
+print(x + y + z)
+
+e, f = range(2)
+print(e)
+print(f)
+
+l = [1, 2]
+l[0], l[1] = l[1], l[0]
+print(l[0])
+print(l[1])
+
+(g, h), k = (1, 2), 3
+print(g + h + k)
+
+# the rhs is evaluated first, then the targets from left to right.
+a = [0, 0, 0]
+i = 0
+i, a[i] = 1, 5
+print(i, a)
+
+b = [0, 0]
+j = b[j] = 1
+print(j, b)
+
+class O:
+    pass
+
+o = O()
+
+def target():
+    print("target")
+    return o
+
+def value():
+    print("value")
+    return 3
+
+target().x = value()
+print(o.x)
+
+for k, b[k] in [(0, 7), (1, 8)]:
+    pass
+print(b)
@@ -0,0 +1,32 @@
+def classify(x):
+    if x < 0:
+        return "negative"
+    elif x < 10:
+        return "small"
+    elif x < 100:
+        return "medium"
+    else:
+        return "large"
+
+print(classify(0 - 5))
+print(classify(3))
+print(classify(42))
+print(classify(1000))
+
+for i in range(4):
+    if i < 1:
+        print("a")
+    elif i < 2:
+        print("b")
+    elif i < 3:
+        print("c")
+
+y = 7
+if y < 5:
+    print("no")
+elif y < 10:
+    if y < 6:
+        print("no")
+    elif y < 8:
+        print("nested")
+print("done")
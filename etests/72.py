@@ -0,0 +1,80 @@
+d = {"a": 1, "b": 2}
+print(d["a"])
+d["c"] = 3
+d["a"] = 10
+print(d["a"])
+print(len(d))
+
+print("b" in d)
+print("z" in d)
+
+del d["b"]
+print("b" in d)
+print(len(d))
+
+print(d.get("c"))
+print(d.get("z"))
+print(d.get("z", 42))
+
+for k in d:
+    print(k)
+
+for k, v in d.items():
+    print(k)
+    print(v)
+
+for v in d.values():
+    print(v)
+
+print(len(d.keys()))
+
+e = {}
+e[1] = "one"
+e[2] = "two"
+print(e[1])
+print(len(e))
+
+e[(1, 2)] = "tuple"
+print(e[(1, 2)])
+
+class Key:
+    def __init__(self, x):
+        self.x = x
+
+k = Key(5)
+e[k] = "obj"
+print(e[k])
+print(Key(5) in e)
+
+try:
+    print(d["missing"])
+except:
+    print("KeyError")
+
+nested = {
+    "x": {"y": 1},
+}
+print(nested["x"]["y"])
+
+def count(words):
+    counts = {}
+    for w in words:
+        counts[w] = counts.get(w, 0) + 1
+    return counts
+
+c = count(["a", "b", "a", "c", "a"])
+print(c["a"])
+print(c["b"])
+
+mixed = {1: "a", 1.0: "b", True: "c"}
+print(mixed, len(mixed), mixed[1.0], 0.0 in {False: 1})
+print({1: 2} == {1: 2}, {1: 2} == {1: 3}, {1: 2} != {2: 2}, {1: {2: 3}} == {True: {2.0: 3}})
+try:
+    print({[1]: 2})
+except TypeError as e:
+    print(e)
+print(str(KeyError("k")), str(KeyError(5)), str(KeyError(1, 2)), repr(KeyError("k")))
+try:
+    mixed[5]
+except KeyError as e:
+    print(e)
@@ -0,0 +1,89 @@
+def f(a, b=2, c=3):
+    print(a)
+    print(b)
+    print(c)
+
+f(1)
+f(1, 20)
+f(1, c=30)
+f(c=300, a=100)
+
+def g(*args, **kwargs):
+    print(len(args))
+    print(len(kwargs))
+    for a in args:
+        print(a)
+    for k, v in kwargs.items():
+        print(k)
+        print(v)
+
+g()
+g(1, 2, 3)
+g(1, x=2, y=3)
+
+def h(a, *rest, key="k", **kw):
+    print(a)
+    print(len(rest))
+    print(key)
+    print(len(kw))
+
+h(1, 2, 3, key="z", extra=4)
+h(1)
+
+xs = [7, 8, 9]
+f(*xs)
+f(*xs[:1], **{"c": 5})
+g(*range(3), **{"q": 1})
+
+def make_default():
+    print("evaluated")
+    return 42
+
+def d(x=make_default()):
+    return x
+
+print(d())
+print(d(1))
+
+class A:
+    def __init__(self, x, y=10):
+        self.x = x
+        self.y = y
+
+    def m(self, z=0, *more):
+        return self.x + self.y + z + len(more)
+
+a = A(1)
+print(a.m())
+print(a.m(5, 6, 7))
+b = A(y=2, x=3)
+print(b.m(z=1))
+
+try:
+    f()
+except:
+    print("too few")
+
+try:
+    f(1, 2, 3, 4)
+except:
+    print("too many")
+
+try:
+    f(1, d=4)
+except:
+    print("unexpected keyword")
+
+try:
+    f(1, a=1)
+except:
+    print("multiple values")
+
+def noargs():
+    return 0
+
+try:
+    noargs(1)
+except:
+    print("no args")
+print(noargs())
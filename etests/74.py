@@ -0,0 +1,112 @@
+def make_counter():
+    count = 0
+    def inc():
+        nonlocal count
+        count += 1
+        return count
+    return inc
+
+c1 = make_counter()
+c2 = make_counter()
+print(c1())
+print(c1())
+print(c2())
+print(c1())
+
+def adder(n):
+    def add(x):
+        return x + n
+    return add
+
+add3 = adder(3)
+add10 = adder(10)
+print(add3(1))
+print(add10(1))
+
+def logged(f):
+    def wrapper(*args, **kwargs):
+        print("calling")
+        r = f(*args, **kwargs)
+        print("done")
+        return r
+    return wrapper
+
+def mul(a, b=2):
+    return a * b
+
+mul = logged(mul)
+print(mul(3))
+print(mul(3, b=5))
+
+def outer():
+    x = 1
+    def middle():
+        def inner():
+            return x
+        return inner
+    f = middle()
+    x = 2
+    return f()
+
+print(outer())
+
+def three():
+    a = 1
+    def two():
+        nonlocal a
+        a = a * 10 + 2
+        def one():
+            nonlocal a
+            a = a * 10 + 3
+        one()
+        return a
+    r = two()
+    return r + a
+
+print(three())
+
+def fact_maker():
+    def fact(n):
+        if n == 0:
+            return 1
+        return n * fact(n - 1)
+    return fact
+
+print(fact_maker()(5))
+
+x = "global"
+
+def with_class():
+    x = "local"
+    class A:
+        x = "class"
+        def get(self):
+            return x
+        y = x
+    class B:
+        z = x
+    print(A().get())
+    print(A.y)
+    print(B.z)
+
+with_class()
+
+def shadow():
+    x = "shadow"
+    def f():
+        global x
+        return x
+    return f()
+
+print(shadow())
+
+def accumulate():
+    total = []
+    def push(v):
+        total.append(v)
+        return len(total)
+    push(1)
+    push(2)
+    return push(3)
+
+print(accumulate())
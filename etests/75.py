@@ -0,0 +1,155 @@
+def count_up(n):
+    i = 0
+    while i < n:
+        yield i
+        i += 1
+
+for x in count_up(3):
+    print(x)
+
+l = list(count_up(4))
+print(len(l))
+print(l[3])
+
+g = count_up(2)
+print(g.__next__())
+print(g.__next__())
+try:
+    g.__next__()
+except:
+    print("exhausted")
+try:
+    g.__next__()
+except:
+    print("still exhausted")
+
+def echo():
+    total = 0
+    while True:
+        x = yield total
+        if x == None:
+            return
+        total += x
+
+e = echo()
+print(e.__next__())
+print(e.send(5))
+print(e.send(10))
+try:
+    e.send(None)
+except:
+    print("returned")
+
+def inner():
+    yield 1
+    yield 2
+
+def outer():
+    yield 0
+    yield from inner()
+    yield from [3, 4]
+    yield 5
+
+for x in outer():
+    print(x)
+
+def delegate():
+    r = yield from echo()
+    yield r
+
+d = delegate()
+d.__next__()
+print(d.send(3))
+print(d.send(4))
+
+def fib():
+    a, b = 0, 1
+    while True:
+        yield a
+        a, b = b, a + b
+
+out = []
+for f in fib():
+    if f > 50:
+        break
+    out.append(f)
+print(len(out))
+print(out[9])
+
+def with_try():
+    try:
+        yield 1
+        yield 2
+    except:
+        yield 100
+
+w = with_try()
+print(w.__next__())
+try:
+    raise 1
+except:
+    print("caught outside")
+print(w.__next__())
+try:
+    w.__next__()
+except:
+    print("done")
+
+def failing():
+    yield 1
+    raise 2
+
+fl = failing()
+print(fl.__next__())
+try:
+    fl.__next__()
+except:
+    print("failed")
+try:
+    fl.__next__()
+except:
+    print("failed again")
+
+def closure_gen(k):
+    def mul(x):
+        return x * k
+    for i in range(3):
+        yield mul(i)
+
+for x in closure_gen(7):
+    print(x)
+
+def empty():
+    return
+    yield 1
+
+print(len(list(empty())))
+print(type(count_up(1)) == type(empty()))
+
+def sub():
+    yield 1
+    return 10
+
+def outer_ret():
+    r = yield from sub()
+    print("ret", r)
+    r = yield from [2]
+    print("ret", r)
+
+print(list(outer_ret()))
+
+def failing():
+    yield 1
+    raise ValueError("inner")
+
+def wrap():
+    try:
+        yield from failing()
+    except ValueError as e:
+        print("caught", e)
+
+print(list(wrap()))
+try:
+    raise StopIteration(7)
+except StopIteration as e:
+    print(e.value, e.args)
@@ -0,0 +1,50 @@
+print(1.5)
+print(1.0)
+print(1e16)
+print(1.5e-5)
+print(2.5e+3)
+print(2E2)
+print(.25 + 1.)
+print(0.1 + 0.2)
+print(12345678.9)
+
+x = 3
+y = 0.5
+print(x + y)
+print(x * y)
+print(y - x)
+print(x / y)
+print(y ** 2)
+print(2 ** y)
+print(-y)
+print(-0.0)
+
+print(x < y)
+print(y < x)
+print(x >= 3.0)
+print(x == 3.0)
+print(3.0 == x)
+print(y == 0.5)
+print(y != 0.5)
+print(1 == 2.0)
+
+if 0.0:
+    print("zero is truthy")
+if y:
+    print("y is truthy")
+
+def mean(a, b):
+    return (a + b) / 2.0
+
+print(mean(1, 2))
+total = 0.0
+i = 0
+while i < 4:
+    total += i * 0.25
+    i += 1
+print(total)
+
+# ints and floats are compared exactly, even beyond 2**53.
+big = 2**53 + 1
+print(big > 9007199254740992.0, big == 9007199254740992.0, 9007199254740992.0 < big)
+print(2**53 == 9007199254740992.0, -2 < -1.5, 3 <= 2.9999, 10**400 > 1e308)
@@ -0,0 +1,37 @@
+print(7 / 2)
+print(-7 // 2)
+print(-7 % 3)
+print(7 % -3)
+print(7.5 // 2)
+print(-7.5 % 2)
+q, r = divmod(-17, 5)
+print(q)
+print(r)
+try:
+    print(1 / 0)
+except ZeroDivisionError:
+    print("zde")
+try:
+    print(1 % 0.0)
+except ZeroDivisionError:
+    print("zde2")
+
+def average(xs):
+    total = 0
+    for x in xs:
+        total += x
+    return total / len(xs)
+
+print(average([1, 2, 3, 4]))
+print(10 // 3 * 3 + 10 % 3)
+print(2 ** 0.5 // 1)
+print(-5 % 2.5)
+print(5 % -2.5)
+try:
+    print(1.0 // 0.0)
+except ZeroDivisionError as e:
+    print(e)
+try:
+    print(1 // 0)
+except ZeroDivisionError as e:
+    print(e)
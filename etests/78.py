@@ -0,0 +1,70 @@
+def fact(n):
+    r = 1
+    while n > 1:
+        r = r * n
+        n = n - 1
+    return r
+
+print(fact(25))
+print(fact(30) // fact(28))
+print(2 ** 100)
+print(2 ** 64 - 1)
+print(-(2 ** 70) // 3)
+print(-(2 ** 70) % 7)
+print(2 ** -2)
+print(10 ** 20 + 0.5)
+print(123456789012345678901234567890)
+print(2 ** 100 > 2 ** 99)
+print(2 ** 64 == 18446744073709551616)
+
+h = 0
+i = 0
+while i < 10:
+    h = h * 31 + i
+    i += 1
+print(h * h * h)
+print(h % 1000000007)
+
+print(10**400 / 10**399, 7 / 2, -1 / 10, 1 / 3, 2**-2000, 10**400 // 10**399)
+print(10**400 < 1.0, 1.0 < 10**400, -10**400 < 1.0, 10**400 == 1e308)
+print(2**1000 / 2**999, (2**64 + 1) / 3, -(10**30) / 7)
+
+def check(f):
+    try:
+        print(f())
+    except OverflowError as e:
+        print("OverflowError", e)
+    except ZeroDivisionError as e:
+        print("ZeroDivisionError", e)
+
+def f0():
+    return float(2**2000)
+check(f0)
+
+def f1():
+    return 10.0**400
+check(f1)
+
+def f2():
+    return 10**400/3
+check(f2)
+
+def f3():
+    return 2**2000*1.0
+check(f3)
+
+def f4():
+    return (2**2000)**-1
+check(f4)
+
+def f5():
+    return 0.0**-1
+check(f5)
+
+def f6():
+    return 2**2000 % 1.0
+check(f6)
+
+def f7():
+    return 1.0 / 2**2000
+check(f7)
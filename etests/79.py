@@ -0,0 +1,85 @@
+class MyError(BaseException):
+    pass
+
+class SubError(MyError):
+    pass
+
+def f(x):
+    if x == 0:
+        raise MyError("zero")
+    if x == 1:
+        raise SubError
+    if x == 2:
+        return 1 / 0
+    return x
+
+for i in [0, 1, 2, 3]:
+    try:
+        print(f(i))
+    except ZeroDivisionError:
+        print("zde")
+    except SubError as e:
+        print("sub")
+        print(len(e.args))
+    except MyError as e:
+        print(e.args[0])
+
+try:
+    try:
+        f(0)
+    except (ZeroDivisionError, SubError):
+        print("wrong")
+except MyError:
+    print("outer")
+
+try:
+    try:
+        f(2)
+    except ZeroDivisionError:
+        print("inner")
+        raise
+except ZeroDivisionError:
+    print("reraised")
+
+try:
+    try:
+        f(1)
+    except MyError as e:
+        try:
+            f(2)
+        except ZeroDivisionError:
+            pass
+        raise
+except SubError:
+    print("reraised sub")
+
+e = MyError("a", 2)
+print(e.args[1])
+try:
+    raise e
+except BaseException as err:
+    print(err.args[0])
+
+try:
+    raise
+except RuntimeError as err:
+    print(err.args[0])
+
+try:
+    f(2)
+except ZeroDivisionError:
+    pass
+try:
+    raise
+except RuntimeError:
+    print("nothing to reraise")
+
+# the name of an except clause is unbound after its handler.
+try:
+    raise ValueError("v")
+except ValueError as err:
+    pass
+try:
+    print(err)
+except NameError:
+    print("unbound")
@@ -0,0 +1,125 @@
+class MyError(BaseException):
+    pass
+
+def normal():
+    try:
+        print("body")
+    finally:
+        print("finally")
+    print("after")
+
+normal()
+
+def caught():
+    try:
+        try:
+            print("raising")
+            raise MyError("boom")
+        finally:
+            print("cleanup")
+        print("not reached")
+    except MyError as e:
+        print(e.args[0])
+
+caught()
+
+def ret():
+    try:
+        return 1
+    finally:
+        print("finally before return")
+
+print(ret())
+
+def ret_override():
+    try:
+        return 1
+    finally:
+        return 2
+
+print(ret_override())
+
+def loops():
+    i = 0
+    while i < 5:
+        i += 1
+        try:
+            if i == 2:
+                continue
+            if i == 4:
+                break
+            print(i)
+        finally:
+            print(i * 10)
+    print(i * 100)
+
+loops()
+
+def nested():
+    try:
+        try:
+            return "inner"
+        finally:
+            print("first")
+    finally:
+        print("second")
+
+print(nested())
+
+def with_else(x):
+    try:
+        if x:
+            raise MyError()
+        print("no exception")
+    except MyError:
+        print("exception")
+    else:
+        print("else")
+    finally:
+        print("finally")
+
+with_else(False)
+with_else(True)
+
+def swallow():
+    while True:
+        try:
+            raise MyError()
+        finally:
+            break
+    return "swallowed"
+
+print(swallow())
+
+def in_handler():
+    try:
+        try:
+            raise MyError()
+        except MyError:
+            return "from handler"
+        finally:
+            print("finally after handler")
+    except MyError:
+        print("not reached")
+
+print(in_handler())
+
+def for_loop():
+    for x in [1, 2, 3]:
+        try:
+            if x == 2:
+                return x
+        finally:
+            print(x * 10)
+
+print(for_loop())
+
+def recurse(n):
+    try:
+        if n == 0:
+            return 0
+        return recurse(n - 1) + 1
+    finally:
+        print(n * 10)
+
+print(recurse(3))
@@ -0,0 +1,98 @@
+class MyError(BaseException):
+    pass
+
+def find(xs, y):
+    for x in xs:
+        if x == y:
+            return x
+    return None
+
+def fail():
+    raise MyError("fail")
+
+def caller():
+    try:
+        find([1, 2, 3], 2)
+        fail()
+    except MyError as e:
+        print(e.args[0])
+    print("caller done")
+
+caller()
+
+def break_out():
+    while True:
+        try:
+            break
+        except MyError:
+            print("not reached")
+    fail()
+
+try:
+    break_out()
+except MyError:
+    print("caught after break")
+
+def continue_out():
+    i = 0
+    while i < 3:
+        i += 1
+        try:
+            continue
+        except MyError:
+            print("not reached")
+    fail()
+
+try:
+    continue_out()
+except MyError:
+    print("caught after continue")
+
+def return_out():
+    try:
+        try:
+            return 1
+        except MyError:
+            print("not reached")
+    except MyError:
+        print("not reached")
+
+def outer():
+    try:
+        return_out()
+        fail()
+    except MyError:
+        print("caught after return")
+
+outer()
+
+def nested_loops():
+    for x in [1, 2]:
+        for y in [3, 4]:
+            try:
+                if y == 4:
+                    break
+            except MyError:
+                print("not reached")
+        print(x)
+    fail()
+
+try:
+    nested_loops()
+except MyError:
+    print("caught after nested loops")
+
+def handler_return():
+    try:
+        fail()
+    except MyError:
+        return "handled"
+
+def outer2():
+    try:
+        handler_return()
+        fail()
+    except MyError:
+        print("caught after handler return")
+
+outer2()
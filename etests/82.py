@@ -0,0 +1,132 @@
+class A:
+    pass
+
+def check(f):
+    try:
+        f()
+        print("no error")
+    except TypeError as e:
+        print("TypeError")
+    except AttributeError as e:
+        print("AttributeError")
+        print(e.args[0])
+    except NameError as e:
+        print("NameError")
+        print(e.args[0])
+    except IndexError as e:
+        print("IndexError")
+        print(e.args[0])
+    except KeyError as e:
+        print("KeyError")
+        print(e.args[0])
+    except ValueError as e:
+        print("ValueError")
+    except ZeroDivisionError as e:
+        print("ZeroDivisionError")
+        print(e.args[0])
+    except StopIteration as e:
+        print("StopIteration")
+    except RecursionError as e:
+        print("RecursionError")
+
+def attr():
+    A().x
+
+def name():
+    undefined_name
+
+def index():
+    [1, 2, 3][3]
+
+def neg_index():
+    return [1, 2, 3][-4]
+
+def tuple_index():
+    (1, 2)[5]
+
+def set_index():
+    l = [1]
+    l[1] = 2
+
+def pop_empty():
+    [].pop()
+
+def key():
+    {"a": 1}["b"]
+
+def unpack():
+    a, b = [1, 2, 3]
+
+def div():
+    1 / 0
+
+def mod():
+    1 % 0
+
+def not_callable():
+    x = 5
+    x()
+
+def operands():
+    1 + [1]
+
+def gen():
+    yield 1
+
+def stop():
+    g = gen()
+    g.__next__()
+    g.__next__()
+
+def recurse():
+    recurse()
+
+def too_many(a):
+    pass
+
+def kw(a):
+    pass
+
+for f in [attr, name, index, neg_index, tuple_index, set_index, pop_empty, key, unpack, div, mod, not_callable, operands, stop, recurse]:
+    check(f)
+
+try:
+    too_many(1, 2)
+except TypeError:
+    print("too many")
+
+try:
+    kw(b=1)
+except TypeError:
+    print("unexpected keyword")
+
+print([1, 2, 3][-1])
+l = [1, 2, 3]
+l[-1] = 4
+print(l[2])
+print(l.pop(-3))
+
+try:
+    raise KeyError("k")
+except LookupError as e:
+    print(e.args[0])
+
+try:
+    1 // 0
+except ArithmeticError:
+    print("arithmetic")
+
+try:
+    raise RecursionError()
+except RuntimeError:
+    print("runtime")
+
+try:
+    raise TypeError()
+except Exception:
+    print("exception")
+
+try:
+    raise ZeroDivisionError("z")
+except BaseException as e:
+    print(e.args[0])
@@ -0,0 +1,25 @@
+class MyError(Exception):
+    pass
+
+def check(x):
+    if x > 2:
+        raise MyError("too big")
+    return x
+
+def run(xs):
+    total = 0
+    i = 0
+    while i < len(xs):
+        total = total + check(xs[i])
+        print(total)
+        i += 1
+    return total
+
+try:
+    raise 5
+except TypeError:
+    print("not an exception")
+
+print(run([1, 2]))
+run([1, 2, 3, 4])
+print("unreachable")
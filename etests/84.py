@@ -0,0 +1,50 @@
+for i in range(3):
+    print(i)
+else:
+    print("else")
+for i in range(5):
+    if i == 2:
+        break
+    print(i)
+else:
+    print("no")
+for x in []:
+    pass
+else:
+    print("empty else")
+def gen():
+    yield 1
+    raise ValueError("boom")
+try:
+    for x in gen():
+        print(x)
+except ValueError as e:
+    print("caught")
+    print(e.args[0])
+class It:
+    def __init__(self):
+        self.n = 0
+    def __iter__(self):
+        return self
+    def __next__(self):
+        self.n += 1
+        if self.n > 3:
+            raise StopIteration
+        return self.n
+for v in It():
+    print(v)
+try:
+    for i in [1]:
+        raise StopIteration
+except StopIteration:
+    print("stop from body propagates")
+try:
+    list(gen())
+except ValueError:
+    print("list propagates")
+print(len(list(It())))
+for a, b in [(1, 2), (3, 4)]:
+    for c in range(2):
+        if c == 1:
+            continue
+        print(a + b + c)
@@ -0,0 +1,41 @@
+print(3 in [1, 2, 3])
+print(4 in [1, 2, 3])
+print(4 not in [1, 2, 3])
+print("a" in {"a": 1})
+print("b" not in {"a": 1})
+print(2 in (1, 2))
+print(5 in range(10))
+x = None
+print(x is None)
+print(x is not None)
+l = [1]
+m = l
+print(l is m)
+print(l is [1])
+print(not True)
+print(not 0)
+print(not 1 == 1)
+print(1 if True else 2)
+print("a" if 0 else "b")
+i = 3
+print(0 < i < 5)
+print(0 < i < 2)
+print(1 < 2 > 0 == 0)
+def f(v):
+    print("eval")
+    return v
+print(1 < f(2) < 3)
+print(5 < f(2) < 3)
+class C:
+    def __contains__(self, x):
+        return x == 7
+print(7 in C())
+print(8 in C())
+def gen():
+    yield 1
+    yield 2
+print(2 in gen())
+print(not 1 in [2])
+print(1 if 0 else 2 if 0 else 3)
+print(True and not False)
+print([] is not None)
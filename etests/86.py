@@ -0,0 +1,104 @@
+x = 7
+x //= 2
+print(x)
+x %= 2
+print(x)
+x **= 5
+print(x)
+x = 2
+x **= 10
+print(x)
+y = 7.5
+y //= 2
+print(y)
+y -= 0.5
+print(y)
+y /= 4
+print(y)
+
+calls = []
+def idx():
+    print("idx")
+    return 0
+l = [10]
+l[idx()] += 5
+print(l[0])
+l[idx()] *= 2
+print(l[0])
+
+class Acc:
+    def __init__(self):
+        self.items = 0
+    def __iadd__(self, n):
+        print("iadd")
+        self.items = self.items + n
+        return self
+    def __add__(self, n):
+        print("add")
+        return self.items + n
+
+a = Acc()
+b = a
+a += 3
+print(a is b)
+print(a.items)
+print(a + 1)
+
+class Bits:
+    def __init__(self, v):
+        self.v = v
+    def __or__(self, o):
+        return Bits(self.v + o)
+    def __iand__(self, o):
+        self.v = self.v * o
+        return self
+    def __rshift__(self, o):
+        return Bits(self.v // (2 ** o))
+    def __imatmul__(self, o):
+        print("matmul")
+        return self
+
+bits = Bits(1)
+bits |= 4
+print(bits.v)
+bits &= 3
+print(bits.v)
+bits >>= 1
+print(bits.v)
+bits @= 0
+print(bits.v)
+
+class Holder:
+    pass
+def get(h):
+    print("get")
+    return h
+h = Holder()
+h.n = 1
+get(h).n += 41
+print(h.n)
+
+print(1 << 10, 1 << 100, -5 >> 1, 5 >> 100, -5 >> 100, 6 & 3, 6 | 3, 6 ^ 3, -6 & 255)
+print(1 + 2 << 3, 1 | 2 ^ 3 & 4, 2 * 3 >> 1, 1 < 2 | 4, -1 & 7)
+print(True & False, True | False, True ^ True, True & 3, 3 | True, True & True is True)
+x = 5
+x <<= 2
+x &= 12
+x |= 1
+x ^= 3
+x >>= 1
+print(x)
+try:
+    1 << -1
+except ValueError as e:
+    print(e)
+try:
+    1.0 & 1
+except TypeError as e:
+    print("type error")
+class A:
+    def __rand__(self, o):
+        return "rand"
+    def __lshift__(self, o):
+        return "lshift"
+print(1 & A(), A() << 2)
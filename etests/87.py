@@ -0,0 +1,116 @@
+class V:
+    def __init__(self, x):
+        self.x = x
+    def __add__(self, o):
+        if isinstance_v(o):
+            return V(self.x + o.x)
+        return V(self.x + o)
+    def __radd__(self, o):
+        print("radd")
+        return V(o + self.x)
+    def __rsub__(self, o):
+        return V(o - self.x)
+    def __lt__(self, o):
+        print("V.lt")
+        return self.x < o.x
+
+def isinstance_v(o):
+    return type(o) is V
+
+print((V(1) + V(2)).x)
+print((V(1) + 5).x)
+print((5 + V(1)).x)
+print((10 - V(3)).x)
+
+class W:
+    def __init__(self, x):
+        self.x = x
+    def __gt__(self, o):
+        print("W.gt")
+        return self.x > o.x
+
+print(V(1) < V(2))
+print(V(1) > W(0) if False else W(2) > V(1))
+
+class N:
+    def __eq__(self, o):
+        print("N.eq")
+        return NotImplemented
+class M:
+    def __eq__(self, o):
+        print("M.eq")
+        return True
+print(N() == M())
+print(M() == N())
+n = N()
+print(n == n)
+print(N() == N())
+print(N() != N())
+
+class Base:
+    def __add__(self, o):
+        return "Base.add"
+    def __radd__(self, o):
+        return "Base.radd"
+class Sub(Base):
+    def __radd__(self, o):
+        return "Sub.radd"
+class Sub2(Base):
+    pass
+print(Base() + Sub())
+print(Base() + Sub2())
+
+class E:
+    def __eq__(self, o):
+        return o == 3
+print(3 == E())
+print(E() == 3)
+print(4 == E())
+
+try:
+    1 + "a"
+except TypeError:
+    print("TypeError")
+try:
+    V(1) - V(2)
+except TypeError as e:
+    print(e)
+try:
+    V(1) + "a"
+except TypeError as e:
+    print(e)
+try:
+    V(1) < 2
+except TypeError as e:
+    print(e)
+try:
+    (5)[0]
+except TypeError as e:
+    print(e)
+n = 1
+try:
+    n **= "a"
+except TypeError as e:
+    print(e)
+class P:
+    def __iadd__(self, o):
+        return NotImplemented
+    def __add__(self, o):
+        return "add fallback"
+p = P()
+p += 1
+print(p)
+print(1 + 2.5)
+print(2 ** 3)
+print(None == None)
+print(1 == 1.0)
+class Q:
+    def __radd__(self, o):
+        return "Q.radd"
+class R(Q):
+    pass
+class S(Q):
+    def __add__(self, o):
+        return "S.add"
+print(S() + R())
+print(1 + R())
@@ -0,0 +1,65 @@
+class A:
+    def __bool__(self):
+        return False
+
+class B:
+    def __len__(self):
+        return 0
+
+class C:
+    def __len__(self):
+        return 3
+
+class D:
+    pass
+
+class E:
+    def __bool__(self):
+        return True
+    def __len__(self):
+        return 0
+
+def check(x):
+    if x:
+        print(1)
+    else:
+        print(0)
+
+check(A())
+check(B())
+check(C())
+check(D())
+check(E())
+check("")
+check("a")
+check([])
+check([0])
+check(())
+check((0,))
+check({})
+check({1: 2})
+check(0)
+check(0.0)
+check(2.5)
+check(None)
+check(True)
+check(False)
+
+print(not A())
+print(not C())
+print(1 if B() else 2)
+
+x = [] or 5
+print(x)
+y = [1] and 7
+print(y)
+z = A() or "fallback"
+print(z)
+w = "" and 3
+print(w == "")
+
+n = 0
+l = [1, 2, 3]
+while l:
+    n = n + l.pop()
+print(n)
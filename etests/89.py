@@ -0,0 +1,48 @@
+print(1, 2, 3)
+print("a", "b", sep="-")
+print("x", end="")
+print("y")
+print(1.5, True, None, False)
+print([1, "a", 2.5, [True, None]])
+print((1,), (), (1, 2))
+d = {1: "x", "k": [1]}
+print(d)
+print({})
+print("it's", repr("it's"), repr("q"))
+class A:
+    pass
+class B:
+    def __repr__(self):
+        return "B()"
+class C(B):
+    def __str__(self):
+        return "C!"
+print(B(), C(), [B(), C()])
+print()
+print("end", end="!")
+print()
+print(sep="", end="")
+try:
+    raise ValueError("bad")
+except ValueError as e:
+    print(e)
+    print(repr(e))
+print(NotImplemented)
+print(-3, 1e20, 0.1)
+try:
+    print(1, sep=2)
+except TypeError:
+    print("te")
+try:
+    print(1, foo=2)
+except TypeError:
+    print("te2")
+print(1, 2, sep=None, end=None)
+print("a", "b", end="|", sep="")
+print("")
+x = [1, (2, 3), {"a": None}]
+s = repr(x)
+print(s)
+print(repr(1.0), repr(True), repr(None))
+print(*[1, 2, 3])
+print(*[1, 2, 3], sep=", ")
@@ -0,0 +1,59 @@
+def gen():
+    for c in "xyz":
+        yield c
+
+s = "Hello, World"
+print(len(s), s[0], s[-1], s[1:5], s[::-1], s[::2], s[-3:])
+print("ab" + "cd", "ab" * 3, 3 * "x", "abc" < "abd", "b" > "abc", "a" <= "a", "b" >= "c")
+print("ell" in s, "xyz" in s, "" in s)
+print(s.find("o"), s.find("zz"), s.startswith("Hell"), s.endswith("ld"), s.endswith("Hello, World!"))
+print(s.find("o", 5), s.find("o", 5, 8), s.find("o", -4), s.find("", 20), s.find("l", None, -8))
+print("a,b,,c".split(","), "  a  b\tc\n ".split(), "".split(), "abc".split("bc"))
+print("-".join(["a", "b", "c"]), "".join(("x", "y")), ",".join(gen()))
+print("  hi  ".strip(), "xxhixx".strip("x"), "  hi  ".lstrip(), "  hi  ".rstrip() + "|")
+print("aaa".replace("a", "bb"), "abc".replace("", "-"), "hello".replace("l", ""))
+print(s.upper(), s.lower())
+for c in "abc":
+    print(c)
+print(list("hey"))
+try:
+    s[100]
+except IndexError as e:
+    print("IndexError", e)
+try:
+    "a".split("")
+except ValueError as e:
+    print("ValueError", e)
+try:
+    1 in "a"
+except TypeError as e:
+    print("TypeError", e)
+try:
+    "-".join([1])
+except TypeError as e:
+    print("TypeError", e)
+print("abc"[1:100], "abc"[-100:2], "abc"[5:], "ab" * 0, "ab" * -2, "abcdef"[4:1:-1], "abcdef"[::-2])
+print("x".startswith(""), "".endswith(""), "".find(""), "abab".find("ba"), "   ".split(), " a ".strip("x"))
+print("a b".split(" "), "ab".replace("ab", ""), "".upper(), "MiXeD 123".lower(), "=" * 3 + ">")
+print(sorted(["b", "a"]) if False else "b" != "a", "a" == "a", "abc" * 2 == "abcabc")
+try:
+    "ab".startswith(1)
+except TypeError as e:
+    print("TypeError", e)
+try:
+    f = 1.5
+    "ab"[f]
+except TypeError as e:
+    print("TypeError", e)
+n = 0
+for c in "hello world":
+    if c in "aeiou":
+        n += 1
+print(n)
+print('a"b')
+print("a\"b", 'it\'s', "tab\there")
+print("x\\y", len("\n"), "\x41é\101", "no\qesc")
+print(repr("a\tb\\"))
+s = "line1\
+line2"
+print(s)
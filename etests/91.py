@@ -0,0 +1,51 @@
+n = int(input())
+print(n * 2, int("12") + 1, int(" -7 "), int("1_000"), int(3.7), int(-3.7), int(True), int(), int(5))
+print(str(5), str(-1.5), str(None), str(True), str("x"), str([1, "a"]), repr(str()), str(3) + "!")
+print(float("1e3"), float(" 2.5 "), float(3), float(False), float(), float("-0.5"), float("1_0.5"))
+print(bool([]), bool([0]), bool(0), bool(""), bool("a"), bool(), bool(None), bool(2.0))
+print(type(int("3")) == int, type(str(1)) == str, type(float(1)) == float, type(bool(1)) == bool)
+
+class A:
+    def __int__(self):
+        return 42
+    def __float__(self):
+        return 1.5
+    def __str__(self):
+        return "an A"
+    def __len__(self):
+        return 0
+
+print(int(A()), float(A()), str(A()), bool(A()))
+
+class B:
+    def __int__(self):
+        return "no"
+
+for bad in ["abc", "1.5", "", "1__0", "_1"]:
+    try:
+        int(bad)
+    except ValueError as e:
+        print("ValueError", e)
+for bad in ["x", "1e", "", "1_"]:
+    try:
+        float(bad)
+    except ValueError as e:
+        print("ValueError", e)
+try:
+    int([1])
+except TypeError as e:
+    print("TypeError", e)
+try:
+    float(None)
+except TypeError as e:
+    print("TypeError", e)
+try:
+    int(B())
+except TypeError as e:
+    print("TypeError", e)
+print(isinstance(True, int), True == 1, 0 == False, 1.0 == True, True + 1, True * 2.5, -True)
+print(type(True + True) == int, True < 2, str(True), repr(False))
+total = 0
+for x in input().split():
+    total += int(x)
+print(total)
@@ -0,0 +1,195 @@
+print(abs(-3), abs(4), abs(-2.5), abs(True), abs(False))
+
+class V:
+    def __init__(self, x):
+        self.x = x
+    def __abs__(self):
+        return self.x * 2
+    def __lt__(self, other):
+        return self.x < other.x
+    def __add__(self, other):
+        return V(self.x + other.x)
+    def __repr__(self):
+        return "V(" + str(self.x) + ")"
+
+print(abs(V(-4)))
+try:
+    abs("x")
+except TypeError as e:
+    print(e)
+
+print(min(3, 1, 2), max(3, 1, 2))
+print(min([4, 2, 8]), max([4, 2, 8]))
+print(min("hello"), max("hello"))
+print(min([], default=7), max([], default=None))
+print(min(["aa", "b", "ccc"], key=len), max(["aa", "b", "ccc"], key=len))
+print(min([1, 1.0]), max([1, 1.0]))
+print(min([V(3), V(1), V(2)]))
+def neg_x(v):
+    return -v.x
+
+print(max(V(3), V(1), V(2), key=neg_x))
+for f in [min, max]:
+    try:
+        f([])
+    except ValueError as e:
+        print(e)
+try:
+    min()
+except TypeError as e:
+    print(e)
+try:
+    max(1, 2, default=3)
+except TypeError as e:
+    print(e)
+try:
+    min([1], foo=3)
+except TypeError as e:
+    print(e)
+
+print(sum([1, 2, 3]), sum([1, 2, 3], 10), sum([0.5, 0.25]), sum([]))
+print(sum([V(1), V(2)], V(10)))
+print(sum(range(5)))
+try:
+    sum(["a", "b"], "")
+except TypeError as e:
+    print(e)
+
+print(sorted([3, 1, 2]), sorted([3, 1, 2], reverse=True))
+print(sorted("hello"))
+print(sorted(["bb", "a", "ccc", "dd"], key=len))
+print(sorted(["bb", "a", "ccc", "dd"], key=len, reverse=True))
+print(sorted([V(2), V(1)]))
+print(sorted([]), sorted((5, 4)))
+try:
+    sorted([1], foo=3)
+except TypeError as e:
+    print(e)
+
+print(list(reversed([1, 2, 3])), list(reversed((4, 5))), list(reversed("abc")))
+print(list(reversed(range(4))), list(reversed(range(2, 5))), list(reversed(range(0))))
+
+class R:
+    def __reversed__(self):
+        return iter([9, 8])
+
+class S:
+    def __len__(self):
+        return 3
+    def __getitem__(self, i):
+        return i * 10
+
+print(list(reversed(R())), list(reversed(S())))
+try:
+    reversed(5)
+except TypeError as e:
+    print(e)
+
+print(list(enumerate("ab")), list(enumerate([7, 8], 1)), list(enumerate([7], start=5)))
+for i, c in enumerate("xy"):
+    print(i, c)
+
+print(list(zip([1, 2, 3], "ab")), list(zip()), list(zip([1, 2])))
+for a, b, c in zip(range(2), "pq", [True, False]):
+    print(a, b, c)
+
+def add(a, b):
+    return a + b
+
+print(list(map(abs, [-1, 2, -3])), list(map(add, [1, 2], [10, 20, 30])))
+print(list(map(str, range(3))))
+
+def odd(x):
+    return x % 2
+
+print(list(filter(odd, range(6))), list(filter(None, [0, 1, "", "a", None])))
+
+print(any([]), any([0, ""]), any([0, 3]), all([]), all([1, "a"]), all([1, 0]))
+def above(n):
+    for x in range(5):
+        yield x > n
+
+print(any(above(2)), all(above(2)), all(above(-1)))
+
+class A:
+    pass
+
+class B(A):
+    pass
+
+print(isinstance(1, int), isinstance("a", int), isinstance("a", (int, str)))
+print(isinstance(B(), A), isinstance(A(), B), isinstance(True, bool))
+print(issubclass(B, A), issubclass(A, B), issubclass(B, (int, A)), issubclass(int, int))
+try:
+    isinstance(1, 2)
+except TypeError as e:
+    print(e)
+try:
+    issubclass(1, int)
+except TypeError as e:
+    print(e)
+
+a = A()
+a.x = 1
+print(hasattr(a, "x"), hasattr(a, "y"), hasattr("s", "upper"))
+print(getattr(a, "x"), getattr(a, "y", 5), getattr("abc", "upper")())
+setattr(a, "y", 2)
+print(a.y)
+try:
+    getattr(a, "z")
+except AttributeError:
+    print("no z")
+try:
+    getattr(a, 1)
+except TypeError as e:
+    print(e)
+try:
+    setattr(1, "x", 2)
+except AttributeError as e:
+    print(e)
+
+b = A()
+print(id(a) == id(a), id(a) == id(b), type(id(a)) == int)
+
+print(hash(1), hash(-1), hash(-5), hash(True), hash(2**70), hash(-(2**70)))
+print(hash("ab") == hash("a" + "b"), hash((1, 2)) == hash((1, 2)), hash(a) == hash(a))
+
+class H:
+    def __hash__(self):
+        return 42
+
+print(hash(H()))
+try:
+    hash([1])
+except TypeError as e:
+    print(e)
+
+it = iter([1, 2])
+print(next(it), next(it), next(it, "done"))
+print(iter(it) is it)
+try:
+    next(it)
+except StopIteration:
+    print("stop")
+try:
+    iter(5)
+except TypeError as e:
+    print(e)
+try:
+    next([1])
+except TypeError as e:
+    print(e)
+
+def gen():
+    yield 1
+    yield 2
+
+g = gen()
+print(next(g), list(g))
+print(len(range(5)), len(range(2, 7)), len(range(7, 2)), bool(range(0)), bool(range(3)))
+print(list(range(10, 0, -3)), len(range(0, 10, 3)), len(range(10, 0, -3)), len(range(0, 10, -1)))
+print(list(reversed(range(0, 10, 3))), list(reversed(range(10, 0, -3))))
+try:
+    range(1, 2, 0)
+except ValueError as e:
+    print(e)
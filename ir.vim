@@ -3,7 +3,7 @@ if exists("b:current_syntax")
   finish
 endif
 
-syntax keyword irKeyword proc jmp exit panic main print fail
+syntax keyword irKeyword proc jmp exit panic main print eprint fail
 syntax match irComment "#.*$"
 syntax region irString start=+"+ skip=+\\."+ end=+"+
 syntax match irNumber /\v(^|[^A-Za-z0-9_])\zs\d+(\.\d+)?\ze([^A-Za-z0-9_]|$)/
@@ -1,15 +1,39 @@
 use crate::*;
 
-pub fn assemble(toks: &[Token]) -> AST {
-    let (ast, toks) = assemble_ast(toks).unwrap();
-    assert!(toks.is_empty(), "{:?}", toks);
+// Every token slice handed to an assembler ends in `TokenKind::Eof`, hence `toks[0]` always exists.
+type Assembled<'a, T> = Result<(T, &'a [Token]), ParseError>;
 
-    ast
+pub fn assemble(toks: &[Token]) -> Result<AST, ParseError> {
+    let (ast, toks) = assemble_ast(toks)?;
+    if toks[0].kind != TokenKind::Eof {
+        return error(toks, "unexpected unindent");
+    }
+
+    Ok(ast)
+}
+
+fn error<T>(toks: &[Token], msg: impl Into<String>) -> Result<T, ParseError> {
+    Err(ParseError { msg: msg.into(), span: toks[0].span })
+}
+
+// consumes the token `kind`, or fails with "expected {what}".
+fn expect_token<'a>(toks: &'a [Token], kind: TokenKind, what: &str) -> Assembled<'a, ()> {
+    if toks[0].kind != kind {
+        return error(toks, format!("expected {what}"));
+    }
+    Ok(((), &toks[1..]))
 }
 
-fn assemble_ast(mut toks: &[Token]) -> Result<(AST, &[Token]), String> {
+// the span of everything that was consumed going from `toks` to `rest`.
+fn span_between(toks: &[Token], rest: &[Token]) -> Span {
+    let n = toks.len() - rest.len();
+    toks[0].span.to(toks[n.max(1) - 1].span)
+}
+
+fn assemble_ast(mut toks: &[Token]) -> Assembled<'_, AST> {
     let mut stmts = Vec::new();
-    while let Ok((x, rst)) = assemble_stmt(toks) {
+    while !matches!(toks[0].kind, TokenKind::Unindent | TokenKind::Eof) {
+        let (x, rst) = assemble_stmt(toks)?;
         toks = rst;
         stmts.push(x);
     }
@@ -18,288 +42,682 @@ fn assemble_ast(mut toks: &[Token]) -> Result<(AST, &[Token]), String> {
 
 // (t1, t2, t3, ...)
 fn assemble_paren_list<T>(sub: impl Assembler<T>) -> impl Assembler<Vec<T>> {
-    assemble_general_list(sub, Token::LParen, Token::RParen)
+    assemble_general_list(sub, TokenKind::LParen, TokenKind::RParen)
 }
 
 // `l` t1, t2, t3, ... `r`
-fn assemble_general_list<T>(sub: impl Assembler<T>, l: Token, r: Token) -> impl Assembler<Vec<T>> {
+fn assemble_general_list<T>(sub: impl Assembler<T>, l: TokenKind, r: TokenKind) -> impl Assembler<Vec<T>> {
     move |toks| {
-        if toks[0] != l {
-            return Err(String::new());
-        }
-        if toks[1] == r {
-            return Ok((Vec::new(), &toks[2..]));
-        }
-        let toks = &toks[1..];
-
-        let (first, mut toks) = sub(toks)?;
-        let mut children = vec![first];
+        let ((), mut toks) = expect_token(toks, l.clone(), "a list")?;
 
-        while toks[0] == Token::Comma {
-            toks = &toks[1..];
+        let mut children = Vec::new();
+        while toks[0].kind != r {
             let (c, rst) = sub(toks)?;
             toks = rst;
             children.push(c);
+
+            if toks[0].kind != TokenKind::Comma { break; }
+            toks = &toks[1..];
         }
-        if toks[0] != r {
-            return Err(String::new());
+
+        let what = match r {
+            TokenKind::RParen => "',' or ')'",
+            TokenKind::RBracket => "',' or ']'",
+            TokenKind::RBrace => "',' or '}'",
+            _ => "',' or the end of the list",
         };
-        let toks = &toks[1..];
+        let ((), toks) = expect_token(toks, r.clone(), what)?;
         Ok((children, toks))
     }
 }
 
-fn assemble_expr(toks: &[Token]) -> Result<(ASTExpr, &[Token]), String> {
-    match toks.get(0) {
-        Some(Token::BinOp(ASTBinOpKind::Minus)) => {
-            return assemble_expr(&toks[1..]).map(|(expr, toks)| {
-                (ASTExpr::UnOp(ASTUnOpKind::Neg, Box::new(expr)), toks)
-            });
+// includes conditional expressions `a if c else b`, which bind the weakest.
+fn assemble_expr(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    let start = toks;
+    let (then, toks) = assemble_binop_expr(toks, 0)?;
+    if toks[0].kind != TokenKind::If { return Ok((then, toks)) };
+
+    let (cond, toks) = assemble_binop_expr(&toks[1..], 0)?;
+    let ((), toks) = expect_token(toks, TokenKind::Else, "'else' in conditional expression")?;
+    let (else_, toks) = assemble_expr(toks)?;
+
+    let expr = ASTExprKind::IfExpr(Box::new(cond), Box::new(then), Box::new(else_));
+    Ok((expr.at(span_between(start, toks)), toks))
+}
+
+// `e1, e2, ...` is a tuple, a single `e` without trailing comma is just `e`.
+fn assemble_expr_list(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    assemble_general_expr_list(assemble_expr)(toks)
+}
+
+// assignment targets like in `for a, b in ...`. They may not contain comparisons, which would swallow the `in`.
+fn assemble_target_list(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    fn assemble_target(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+        assemble_binop_expr(toks, binop_prec(ASTBinOpKind::Plus))
+    }
+    assemble_general_expr_list(assemble_target)(toks)
+}
+
+// like assemble_expr_list, but also allows for a yield expression, as in `x = yield y`.
+fn assemble_expr_list_or_yield(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    match toks[0].kind {
+        TokenKind::Yield => assemble_yield_expr(toks),
+        _ => assemble_expr_list(toks),
+    }
+}
+
+// `yield`, `yield e1, e2, ...` or `yield from e`.
+fn assemble_yield_expr(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    let start = toks;
+    let toks = &toks[1..];
+    let (kind, toks) = match toks[0].kind {
+        TokenKind::From => {
+            let (e, toks) = assemble_expr(&toks[1..])?;
+            (ASTExprKind::YieldFrom(Box::new(e)), toks)
+        },
+        ref k if ends_expr_list(k) => (ASTExprKind::Yield(None), toks),
+        _ => {
+            let (e, toks) = assemble_expr_list(toks)?;
+            (ASTExprKind::Yield(Some(Box::new(e))), toks)
+        },
+    };
+    Ok((kind.at(span_between(start, toks)), toks))
+}
+
+fn assemble_general_expr_list(sub: impl Assembler<ASTExpr>) -> impl Assembler<ASTExpr> {
+    move |toks| {
+        let start = toks;
+        let (first, mut toks) = sub(toks)?;
+        if toks[0].kind != TokenKind::Comma {
+            return Ok((first, toks));
+        }
+
+        let mut elems = vec![first];
+        while toks[0].kind == TokenKind::Comma {
+            toks = &toks[1..];
+            // trailing comma.
+            if ends_expr_list(&toks[0].kind) { break; }
+
+            let (e, toks2) = sub(toks)?;
+            toks = toks2;
+            elems.push(e);
+        }
+        Ok((ASTExprKind::Tuple(elems).at(span_between(start, toks)), toks))
+    }
+}
+
+fn ends_expr_list(kind: &TokenKind) -> bool {
+    matches!(kind,
+        TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace | TokenKind::Newline | TokenKind::Eof
+            | TokenKind::Equals | TokenKind::AugOp(_) | TokenKind::Colon | TokenKind::In
+    )
+}
+
+// Binding power of the binary operators, higher binds tighter.
+// See https://docs.python.org/3/reference/expressions.html#operator-precedence
+fn binop_prec(op: ASTBinOpKind) -> usize {
+    match op {
+        ASTBinOpKind::Or => 1,
+        ASTBinOpKind::And => 2,
+        // 3 is `not`, see NOT_PREC.
+        ASTBinOpKind::Lt | ASTBinOpKind::Le | ASTBinOpKind::Gt | ASTBinOpKind::Ge
+            | ASTBinOpKind::IsEqual | ASTBinOpKind::IsNotEqual | ASTBinOpKind::In | ASTBinOpKind::NotIn
+            | ASTBinOpKind::Is | ASTBinOpKind::IsNot => COMPARISON_PREC,
+        ASTBinOpKind::BitOr => 5,
+        ASTBinOpKind::BitXor => 6,
+        ASTBinOpKind::BitAnd => 7,
+        ASTBinOpKind::LShift | ASTBinOpKind::RShift => 8,
+        ASTBinOpKind::Plus | ASTBinOpKind::Minus => 9,
+        ASTBinOpKind::Mul | ASTBinOpKind::Div | ASTBinOpKind::FloorDiv | ASTBinOpKind::Mod => 10,
+        // 11 is unary `-`, see UNARY_PREC.
+        ASTBinOpKind::Pow => 12,
+
+        ASTBinOpKind::Subscript => unreachable!(),
+    }
+}
+
+const NOT_PREC: usize = 3;
+const COMPARISON_PREC: usize = 4;
+const UNARY_PREC: usize = 11;
+
+fn is_comparison(op: ASTBinOpKind) -> bool {
+    op != ASTBinOpKind::Subscript && binop_prec(op) == COMPARISON_PREC
+}
+
+// the binary operator at the start of `toks`, and how many tokens it takes.
+// `in` is a keyword token, as it's also part of `for` loops.
+fn token_binop(toks: &[Token]) -> Option<(ASTBinOpKind, usize)> {
+    match (&toks[0].kind, toks.get(1).map(|t| &t.kind)) {
+        (TokenKind::BinOp(op), _) => Some((*op, 1)),
+        (TokenKind::In, _) => Some((ASTBinOpKind::In, 1)),
+        (TokenKind::Not, Some(TokenKind::In)) => Some((ASTBinOpKind::NotIn, 2)),
+        (TokenKind::Is, Some(TokenKind::Not)) => Some((ASTBinOpKind::IsNot, 2)),
+        (TokenKind::Is, _) => Some((ASTBinOpKind::Is, 1)),
+        _ => None,
+    }
+}
+
+fn binop_right_assoc(op: ASTBinOpKind) -> bool {
+    matches!(op, ASTBinOpKind::Pow)
+}
+
+// precedence climbing: parses a chain of binary operators, that all bind at least as tight as `min_prec`.
+fn assemble_binop_expr(toks: &[Token], min_prec: usize) -> Assembled<'_, ASTExpr> {
+    let start = toks;
+    let (mut expr, mut toks) = assemble_unary_expr(toks)?;
+    while let Some((op, len)) = token_binop(toks) {
+        let prec = binop_prec(op);
+        if prec < min_prec { break; }
+
+        if is_comparison(op) {
+            let (e, toks2) = assemble_comparison(expr, toks)?;
+            expr = e.at(span_between(start, toks2));
+            toks = toks2;
+            continue;
+        }
+
+        let rhs_prec = if binop_right_assoc(op) { prec } else { prec + 1 };
+        let (rhs, toks2) = assemble_binop_expr(&toks[len..], rhs_prec)?;
+        toks = toks2;
+        expr = ASTExprKind::BinOp(op, Box::new(expr), Box::new(rhs)).at(span_between(start, toks));
+    }
+    Ok((expr, toks))
+}
+
+// `a < b < c` is a single chain of comparisons, rather than `(a < b) < c`.
+fn assemble_comparison(first: ASTExpr, mut toks: &[Token]) -> Assembled<'_, ASTExprKind> {
+    let mut chain = Vec::new();
+    while let Some((op, len)) = token_binop(toks) {
+        if !is_comparison(op) { break; }
+
+        let (rhs, toks2) = assemble_binop_expr(&toks[len..], COMPARISON_PREC + 1)?;
+        toks = toks2;
+        chain.push((op, rhs));
+    }
+
+    if chain.len() == 1 {
+        let (op, rhs) = chain.pop().unwrap();
+        return Ok((ASTExprKind::BinOp(op, Box::new(first), Box::new(rhs)), toks));
+    }
+    Ok((ASTExprKind::Compare(Box::new(first), chain), toks))
+}
+
+fn assemble_unary_expr(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    match toks[0].kind {
+        TokenKind::BinOp(ASTBinOpKind::Minus) => {
+            // `-a ** b` is `-(a ** b)`, but `-a * b` is `(-a) * b`.
+            let (expr, rest) = assemble_binop_expr(&toks[1..], UNARY_PREC)?;
+            let expr = ASTExprKind::UnOp(ASTUnOpKind::Neg, Box::new(expr));
+            Ok((expr.at(span_between(toks, rest)), rest))
+        },
+        TokenKind::Not => {
+            // `not a == b` is `not (a == b)`, but `not a and b` is `(not a) and b`.
+            let (expr, rest) = assemble_binop_expr(&toks[1..], NOT_PREC)?;
+            let expr = ASTExprKind::UnOp(ASTUnOpKind::Not, Box::new(expr));
+            Ok((expr.at(span_between(toks, rest)), rest))
         },
-        _ => {},
+        _ => assemble_postfix_expr(toks),
     }
+}
+
+// atomic expressions followed by calls, attribute accesses and subscripts.
+fn assemble_postfix_expr(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    let start = toks;
     let (mut expr, mut toks) = assemble_atomic_expr(toks)?;
     loop {
-        match toks.get(0) {
-            Some(Token::LParen) => {
-                let (children, toks2) = assemble_paren_list(assemble_expr)(toks)?;
-                expr = ASTExpr::FnCall(Box::new(expr), children);
-                toks = toks2;
-            }
-            Some(Token::BinOp(op)) => {
-                let (rhs, toks2) = assemble_expr(&toks[1..])?;
-                expr = ASTExpr::BinOp(*op, Box::new(expr), Box::new(rhs));
+        let kind = match toks[0].kind {
+            TokenKind::LParen => {
+                let (children, toks2) = assemble_paren_list(assemble_arg)(toks)?;
+                check_args(&children)?;
                 toks = toks2;
+                ASTExprKind::FnCall(Box::new(expr), children)
             }
-            Some(Token::Dot) => {
+            TokenKind::Dot => {
                 let (rhs, toks2) = assemble_ident(&toks[1..])?;
-                expr = ASTExpr::Attribute(Box::new(expr), rhs);
                 toks = toks2;
+                ASTExprKind::Attribute(Box::new(expr), rhs)
             },
-            Some(Token::LBracket) => {
-                if let Ok((idx, [Token::RBracket, toks2@..])) = assemble_expr(&toks[1..]) {
-                    expr = ASTExpr::BinOp(ASTBinOpKind::Subscript, Box::new(expr), Box::new(idx));
-                    toks = toks2;
-                } else { // parse a slice
-                    toks = &toks[1..];
-                    let mut a: Option<ASTExpr> = None;
-                    let mut b: Option<ASTExpr> = None;
-                    let mut c: Option<ASTExpr> = None;
-
-                    // parsing "a".
-                    if let Ok((aa, toks2)) = assemble_expr(&toks) {
-                        a = Some(aa);
-                        toks = toks2;
-                    }
-                    let [Token::Colon, toks2@..] = toks else { return Err(String::new()) };
-                    toks = toks2;
-
-                    // parsing "b"
-                    if let Ok((bb, toks2)) = assemble_expr(&toks) {
-                        b = Some(bb);
-                        toks = toks2;
-                    }
-
-                    if let [Token::Colon, toks2@..] = toks {
-                        toks = toks2;
-                    } else if let [Token::RBracket, toks2@..] = toks {
-                        let slice = ASTExpr::Slice(Box::new((a, b, c)));
-                        expr = ASTExpr::BinOp(ASTBinOpKind::Subscript, Box::new(expr), Box::new(slice));
-                        toks = toks2;
-                        continue;
-                    } else { return Err(String::new()) }
-
-                    // parsing "c"
-                    if let Ok((cc, toks2)) = assemble_expr(&toks) {
-                        c = Some(cc);
-                        toks = toks2;
-                    }
-
-                    let [Token::RBracket, toks2@..] = toks else { return Err(String::new()) };
-
-                    let slice = ASTExpr::Slice(Box::new((a, b, c)));
-                    expr = ASTExpr::BinOp(ASTBinOpKind::Subscript, Box::new(expr), Box::new(slice));
-                    toks = toks2;
-                }
+            TokenKind::LBracket => {
+                let (idx, toks2) = assemble_subscript(&toks[1..])?;
+                let ((), toks2) = expect_token(toks2, TokenKind::RBracket, "']'")?;
+                toks = toks2;
+                ASTExprKind::BinOp(ASTBinOpKind::Subscript, Box::new(expr), Box::new(idx))
             },
             _ => return Ok((expr, toks)),
-        }
+        };
+        expr = kind.at(span_between(start, toks));
     }
 }
 
-fn assemble_atomic_expr(toks: &[Token]) -> Result<(ASTExpr, &[Token]), String> {
-    match toks.get(0) {
-        Some(Token::Ident(x)) => Ok((ASTExpr::Var(x.clone()), &toks[1..])),
-        Some(Token::Int(x)) => Ok((ASTExpr::Int(*x), &toks[1..])),
-        Some(Token::Str(s)) => Ok((ASTExpr::Str(s.to_string()), &toks[1..])),
-        Some(Token::Bool(b)) => Ok((ASTExpr::Bool(*b), &toks[1..])),
-        Some(Token::None) => Ok((ASTExpr::None, &toks[1..])),
-        Some(Token::LBracket) => {
-            let (list, toks) = assemble_general_list(assemble_expr, Token::LBracket, Token::RBracket)(toks)?;
-            Ok((ASTExpr::List(list), toks))
+// a single argument of a call.
+fn assemble_arg(toks: &[Token]) -> Assembled<'_, ASTArg> {
+    match (&toks[0].kind, &toks[1].kind) {
+        (TokenKind::BinOp(ASTBinOpKind::Mul), _) => {
+            let (e, toks) = assemble_expr(&toks[1..])?;
+            Ok((ASTArg::Star(e), toks))
+        },
+        (TokenKind::BinOp(ASTBinOpKind::Pow), _) => {
+            let (e, toks) = assemble_expr(&toks[1..])?;
+            Ok((ASTArg::DoubleStar(e), toks))
         },
-        Some(Token::LParen) => {
-            let (children, toks) = assemble_paren_list(assemble_expr)(toks)?;
-            assert!(children.len() == 1, "tuples aren't supported yet!");
-            let c = children[0].clone();
-            Ok((c, toks))
+        (TokenKind::Ident(name), TokenKind::Equals) => {
+            let (e, toks) = assemble_expr(&toks[2..])?;
+            Ok((ASTArg::Keyword(name.clone(), e), toks))
         },
-        _ => Err(String::new()),
+        _ => {
+            let (e, toks) = assemble_expr(toks)?;
+            Ok((ASTArg::Positional(e), toks))
+        },
+    }
+}
+
+// `f(a=1, 2)` and `f(**kw, *xs)` are not allowed, `f(a=1, *xs)` is.
+fn check_args(args: &[ASTArg]) -> Result<(), ParseError> {
+    let mut seen_kw = false;
+    let mut seen_double_star = false;
+    for a in args {
+        let msg = match a {
+            ASTArg::Positional(e) if seen_kw || seen_double_star => (e, "positional argument follows keyword argument"),
+            ASTArg::Star(e) if seen_double_star => (e, "iterable argument unpacking follows keyword argument unpacking"),
+            ASTArg::Keyword(..) => { seen_kw = true; continue },
+            ASTArg::DoubleStar(_) => { seen_double_star = true; continue },
+            _ => continue,
+        };
+        let (e, msg) = msg;
+        return Err(ParseError { msg: msg.to_string(), span: e.span });
+    }
+    Ok(())
+}
+
+// the part within `x[...]`, either a plain index or a slice `a:b:c`.
+fn assemble_subscript(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    let start = toks;
+
+    // parsing "a".
+    let (a, toks) = assemble_slice_part(toks)?;
+    if toks[0].kind != TokenKind::Colon {
+        let Some(a) = a else { return error(toks, "expected index") };
+        return Ok((a, toks));
+    }
+    let toks = &toks[1..];
+
+    // parsing "b".
+    let (b, mut toks) = assemble_slice_part(toks)?;
+
+    // parsing "c".
+    let mut c = None;
+    if toks[0].kind == TokenKind::Colon {
+        let (cc, toks2) = assemble_slice_part(&toks[1..])?;
+        c = cc;
+        toks = toks2;
+    }
+
+    let slice = ASTExprKind::Slice(Box::new((a, b, c)));
+    Ok((slice.at(span_between(start, toks)), toks))
+}
+
+fn assemble_slice_part(toks: &[Token]) -> Assembled<'_, Option<ASTExpr>> {
+    if matches!(toks[0].kind, TokenKind::Colon | TokenKind::RBracket) {
+        return Ok((None, toks));
+    }
+    let (e, toks) = assemble_expr(toks)?;
+    Ok((Some(e), toks))
+}
+
+fn assemble_atomic_expr(toks: &[Token]) -> Assembled<'_, ASTExpr> {
+    let span = toks[0].span;
+    match &toks[0].kind {
+        TokenKind::Ident(x) => Ok((ASTExprKind::Var(x.clone()).at(span), &toks[1..])),
+        TokenKind::Int(x) => Ok((ASTExprKind::Int(x.clone()).at(span), &toks[1..])),
+        TokenKind::Float(x) => Ok((ASTExprKind::Float(*x).at(span), &toks[1..])),
+        TokenKind::Str(s) => Ok((ASTExprKind::Str(s.to_string()).at(span), &toks[1..])),
+        TokenKind::Bool(b) => Ok((ASTExprKind::Bool(*b).at(span), &toks[1..])),
+        TokenKind::None => Ok((ASTExprKind::None.at(span), &toks[1..])),
+        TokenKind::LBracket => {
+            let (list, rest) = assemble_general_list(assemble_expr, TokenKind::LBracket, TokenKind::RBracket)(toks)?;
+            Ok((ASTExprKind::List(list).at(span_between(toks, rest)), rest))
+        },
+        TokenKind::LBrace => {
+            let (pairs, rest) = assemble_general_list(assemble_dict_pair, TokenKind::LBrace, TokenKind::RBrace)(toks)?;
+            Ok((ASTExprKind::Dict(pairs).at(span_between(toks, rest)), rest))
+        },
+        TokenKind::LParen if toks[1].kind == TokenKind::RParen => {
+            Ok((ASTExprKind::Tuple(Vec::new()).at(span_between(toks, &toks[2..])), &toks[2..]))
+        },
+        TokenKind::LParen => {
+            let (expr, rest) = assemble_expr_list_or_yield(&toks[1..])?;
+            let ((), rest) = expect_token(rest, TokenKind::RParen, "')'")?;
+            // the parens are part of the tuple.
+            let expr = match expr.kind {
+                ASTExprKind::Tuple(elems) => ASTExprKind::Tuple(elems).at(span_between(toks, rest)),
+                _ => expr,
+            };
+            Ok((expr, rest))
+        },
+        _ => error(toks, "expected expression"),
     }
 }
 
-fn assemble_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let a = assemble_stmt_base;
-    let a = or(a, assemble_def_stmt);
-    let a = or(a, assemble_class_stmt);
-    let a = or(a, assemble_expr_stmt);
-    let a = or(a, assemble_branch_stmt);
-    let a = or(a, assemble_for_stmt);
-    let a = or(a, assemble_try_stmt);
-    let a = or(a, assemble_raise_stmt);
-    a(toks)
+// k: v
+fn assemble_dict_pair(toks: &[Token]) -> Assembled<'_, (ASTExpr, ASTExpr)> {
+    let (k, toks) = assemble_expr(toks)?;
+    let ((), toks) = expect_token(toks, TokenKind::Colon, "':' after dict key")?;
+    let (v, toks) = assemble_expr(toks)?;
+    Ok(((k, v), toks))
+}
+
+fn assemble_stmt(toks: &[Token]) -> Assembled<'_, ASTStatement> {
+    let (kind, rest) = match toks[0].kind {
+        TokenKind::Def => assemble_def_stmt(toks)?,
+        TokenKind::Class => assemble_class_stmt(toks)?,
+        TokenKind::If | TokenKind::While => assemble_branch_stmt(toks)?,
+        TokenKind::For => assemble_for_stmt(toks)?,
+        TokenKind::Try => assemble_try_stmt(toks)?,
+        TokenKind::Indent => return error(toks, "unexpected indent"),
+        TokenKind::Elif => return error(toks, "'elif' without matching 'if'"),
+        _ => return assemble_simple_stmt(toks),
+    };
+    Ok((kind.at(span_between(toks, rest)), rest))
+}
+
+// a statement that fits into a single line.
+fn assemble_simple_stmt(toks: &[Token]) -> Assembled<'_, ASTStatement> {
+    let (kind, rest) = match toks[0].kind {
+        TokenKind::Raise => assemble_raise_stmt(toks)?,
+        TokenKind::Del => assemble_del_stmt(toks)?,
+        TokenKind::Break | TokenKind::Continue | TokenKind::Pass
+            | TokenKind::Scope(_) | TokenKind::Return => assemble_stmt_base(toks)?,
+        _ => assemble_expr_stmt(toks)?,
+    };
+    let span = span_between(toks, rest);
+    let ((), rest) = expect_token(rest, TokenKind::Newline, "end of line after statement")?;
+    Ok((kind.at(span), rest))
 }
 
-fn assemble_raise_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let Some(Token::Raise) = toks.get(0) else { return Err("".to_string()) };
+fn assemble_raise_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
     let toks = &toks[1..];
+    if toks[0].kind == TokenKind::Newline {
+        return Ok((ASTStatementKind::Raise(None), toks));
+    }
     let (expr, toks) = assemble_expr(toks)?;
-    Ok((ASTStatement::Raise(expr), toks))
+    Ok((ASTStatementKind::Raise(Some(expr)), toks))
+}
+
+fn assemble_del_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (targets, toks) = assemble_general_expr_list(assemble_expr)(&toks[1..])?;
+    let targets = match targets.kind {
+        ASTExprKind::Tuple(elems) => elems,
+        _ => vec![targets],
+    };
+    for t in &targets {
+        check_target(t, TargetKind::Del)?;
+    }
+    Ok((ASTStatementKind::Del(targets), toks))
 }
 
-fn assemble_try_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let [Token::Try, toks@..] = toks else { return Err("".to_string()) };
-    let (body, toks) = assemble_indented_ast(toks)?;
+fn assemble_try_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (body, toks) = assemble_indented_ast(&toks[1..], "':' after 'try'")?;
 
     let mut excepts = Vec::new();
     let mut toks = toks;
-    while let [Token::Except, toks2@..] = toks {
+    while toks[0].kind == TokenKind::Except {
         let mut ty = None;
-        let mut toks2 = toks2;
-        if !matches!(toks2.get(0), Some(Token::Colon)) {
+        let mut name = None;
+        let mut toks2 = &toks[1..];
+        if toks2[0].kind != TokenKind::Colon {
             let (ty2, toks3) = assemble_expr(toks2)?;
             toks2 = toks3;
             ty = Some(ty2);
+
+            if toks2[0].kind == TokenKind::As {
+                let (name2, toks3) = assemble_ident(&toks2[1..])?;
+                toks2 = toks3;
+                name = Some(name2);
+            }
         }
-        let (body, toks2) = assemble_indented_ast(toks2)?;
+        let (body, toks2) = assemble_indented_ast(toks2, "':' after except clause")?;
         toks = toks2;
-        let except = Except { body, ty };
+        let except = Except { body, ty, name };
         excepts.push(except);
     }
-    if excepts.is_empty() { return Err("no excepts!".to_string()); }
 
-    Ok((ASTStatement::Try(body, excepts), toks))
+    // `else` is only allowed after except clauses.
+    let mut else_ = None;
+    if toks[0].kind == TokenKind::Else && !excepts.is_empty() {
+        let (else_body, toks2) = assemble_indented_ast(&toks[1..], "':' after 'else'")?;
+        toks = toks2;
+        else_ = Some(else_body);
+    }
+
+    let mut finally = None;
+    if toks[0].kind == TokenKind::Finally {
+        let (finally_body, toks2) = assemble_indented_ast(&toks[1..], "':' after 'finally'")?;
+        toks = toks2;
+        finally = Some(finally_body);
+    }
+
+    if excepts.is_empty() && finally.is_none() { return error(toks, "expected 'except' or 'finally' after try block"); }
+
+    Ok((ASTStatementKind::Try(body, excepts, else_, finally), toks))
 }
 
-fn assemble_branch_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let f = match toks.get(0) {
-        Some(Token::If) => ASTStatement::If,
-        Some(Token::While) => ASTStatement::While,
-        _ => return Err(String::new()),
+fn assemble_branch_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (f, what): (fn(_, _, _) -> _, _) = match toks[0].kind {
+        TokenKind::If => (ASTStatementKind::If, "':' after if condition"),
+        TokenKind::Elif => (ASTStatementKind::If, "':' after elif condition"),
+        TokenKind::While => (ASTStatementKind::While, "':' after while condition"),
+        _ => unreachable!(),
     };
+    let is_while = toks[0].kind == TokenKind::While;
     let toks = &toks[1..];
     let (expr, toks) = assemble_expr(toks)?;
-    let (body, toks) = assemble_indented_ast(toks)?;
+    let (body, toks) = assemble_indented_ast(toks, what)?;
 
-    let [Token::Else, toks@..] = toks else { return Ok((f(expr, body, None), toks)) };
-    let (else_body, toks) = assemble_indented_ast(toks)?;
+    // `elif c: ..` is sugar for `else: if c: ..`.
+    if toks[0].kind == TokenKind::Elif && !is_while {
+        let (elif, rest) = assemble_branch_stmt(toks)?;
+        let elif = elif.at(span_between(toks, rest));
+        return Ok((f(expr, body, Some(vec![elif])), rest));
+    }
+
+    if toks[0].kind != TokenKind::Else { return Ok((f(expr, body, None), toks)) };
+    let (else_body, toks) = assemble_indented_ast(&toks[1..], "':' after 'else'")?;
 
     Ok((f(expr, body, Some(else_body)), toks))
 }
 
-fn assemble_for_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let [Token::For, Token::Ident(v), Token::In, toks@..] = toks else { return Err(String::new()) };
-    let v = v.to_string();
-    let (expr, toks) = assemble_expr(toks)?;
-    let (body, toks) = assemble_indented_ast(toks)?;
-    Ok((ASTStatement::For(v, expr, body), toks))
-}
-
-fn assemble_stmt_base(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    match toks.get(0) {
-        Some(Token::Break) => Ok((ASTStatement::Break, &toks[1..])),
-        Some(Token::Continue) => Ok((ASTStatement::Continue, &toks[1..])),
-        Some(Token::Pass) => Ok((ASTStatement::Pass, &toks[1..])),
-        Some(Token::Scope(kind)) => {
-            let (id, toks) = assemble_ident(&toks[1..])?;
-            Ok((ASTStatement::Scope(*kind, vec![id]), toks))
+fn assemble_for_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (target, toks) = assemble_target_list(&toks[1..])?;
+    check_target(&target, TargetKind::Assign)?;
+    let ((), toks) = expect_token(toks, TokenKind::In, "'in' after loop variable")?;
+    let (expr, toks) = assemble_expr_list(toks)?;
+    let (body, toks) = assemble_indented_ast(toks, "':' after for loop iterable")?;
+
+    if toks[0].kind != TokenKind::Else { return Ok((ASTStatementKind::For(target, expr, body, None), toks)) };
+    let (else_body, toks) = assemble_indented_ast(&toks[1..], "':' after 'else'")?;
+
+    Ok((ASTStatementKind::For(target, expr, body, Some(else_body)), toks))
+}
+
+fn assemble_stmt_base(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    match toks[0].kind {
+        TokenKind::Break => Ok((ASTStatementKind::Break, &toks[1..])),
+        TokenKind::Continue => Ok((ASTStatementKind::Continue, &toks[1..])),
+        TokenKind::Pass => Ok((ASTStatementKind::Pass, &toks[1..])),
+        TokenKind::Scope(kind) => {
+            let (ids, toks) = assemble_ident_list(&toks[1..])?;
+            Ok((ASTStatementKind::Scope(kind, ids), toks))
         }
-        Some(Token::Return) => match assemble_expr(&toks[1..]) {
-            Ok((expr, toks)) => Ok((ASTStatement::Return(Some(expr)), toks)),
-            Err(_) => Ok((ASTStatement::Return(None), &toks[1..])),
+        TokenKind::Return if toks[1].kind == TokenKind::Newline => Ok((ASTStatementKind::Return(None), &toks[1..])),
+        TokenKind::Return => {
+            let (expr, toks) = assemble_expr_list(&toks[1..])?;
+            Ok((ASTStatementKind::Return(Some(expr)), toks))
         },
-        _ => Err(String::new()),
+        _ => unreachable!(),
     }
 }
 
-fn assemble_ident(toks: &[Token]) -> Result<(String, &[Token]), String> {
-    let Some(Token::Ident(ident_name)) = toks.get(0) else {
-        return Err(String::new());
+fn assemble_ident(toks: &[Token]) -> Assembled<'_, String> {
+    let TokenKind::Ident(ident_name) = &toks[0].kind else {
+        return error(toks, "expected identifier");
     };
     let toks = &toks[1..];
     Ok((ident_name.to_string(), toks))
 }
 
-fn assemble_token(t: Token) -> impl Assembler<()> {
-    let opt = Some(t);
-    move |toks| {
-        if opt.as_ref() == toks.get(0) {
-            Ok(((), &toks[1..]))
-        } else {
-            Err(String::new())
-        }
+// a, b, c
+fn assemble_ident_list(toks: &[Token]) -> Assembled<'_, Vec<String>> {
+    let (first, mut toks) = assemble_ident(toks)?;
+    let mut ids = vec![first];
+    while toks[0].kind == TokenKind::Comma {
+        let (id, toks2) = assemble_ident(&toks[1..])?;
+        toks = toks2;
+        ids.push(id);
     }
+    Ok((ids, toks))
 }
 
-fn assemble_def_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let ((), toks) = assemble_token(Token::Def)(toks)?;
-    let (fn_name, toks) = assemble_ident(toks)?;
-    let (children, toks) = assemble_paren_list(assemble_ident)(toks)?;
-    let (body, toks) = assemble_indented_ast(toks)?;
-    Ok((ASTStatement::Def(fn_name, children, body), toks))
+fn assemble_def_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (fn_name, toks) = assemble_ident(&toks[1..])?;
+    let (params, rest) = assemble_paren_list(assemble_param)(toks)?;
+    check_params(&params, toks)?;
+    let (body, toks) = assemble_indented_ast(rest, "':' after function signature")?;
+    Ok((ASTStatementKind::Def(fn_name, params, body), toks))
 }
 
-fn assemble_class_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let ((), toks) = assemble_token(Token::Class)(toks)?;
-    let (class_name, toks) = assemble_ident(toks)?;
-    let (children, toks) = if let Some(Token::LParen) = toks.get(0) {
+// a, a=default, *args or **kwargs
+fn assemble_param(toks: &[Token]) -> Assembled<'_, Param> {
+    let (kind, toks) = match toks[0].kind {
+        TokenKind::BinOp(ASTBinOpKind::Mul) => (ParamKind::VarArgs, &toks[1..]),
+        TokenKind::BinOp(ASTBinOpKind::Pow) => (ParamKind::VarKwArgs, &toks[1..]),
+        _ => (ParamKind::Normal, toks),
+    };
+    let (name, toks) = assemble_ident(toks)?;
+    if kind != ParamKind::Normal || toks[0].kind != TokenKind::Equals {
+        return Ok((Param { name, kind, default: None }, toks));
+    }
+    let (default, toks) = assemble_expr(&toks[1..])?;
+    Ok((Param { name, kind, default: Some(default) }, toks))
+}
+
+// the order has to be: positional params, *args, keyword-only params, **kwargs.
+fn check_params(params: &[Param], toks: &[Token]) -> Result<(), ParseError> {
+    let mut seen_default = false;
+    let mut seen_varargs = false;
+    let mut seen_varkwargs = false;
+    for (i, p) in params.iter().enumerate() {
+        if params[..i].iter().any(|p2| p2.name == p.name) {
+            return error(toks, format!("duplicate argument '{}' in function definition", p.name));
+        }
+        if seen_varkwargs {
+            return error(toks, "arguments cannot follow '**' parameter");
+        }
+        match p.kind {
+            ParamKind::VarArgs if seen_varargs => return error(toks, "'*' parameter given twice"),
+            ParamKind::VarArgs => seen_varargs = true,
+            ParamKind::VarKwArgs => seen_varkwargs = true,
+            ParamKind::Normal if p.default.is_some() => seen_default = true,
+            ParamKind::Normal if seen_default && !seen_varargs => {
+                return error(toks, "non-default argument follows default argument");
+            },
+            ParamKind::Normal => {},
+        }
+    }
+    Ok(())
+}
+
+fn assemble_class_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (class_name, toks) = assemble_ident(&toks[1..])?;
+    let (children, toks) = if toks[0].kind == TokenKind::LParen {
         assemble_paren_list(assemble_expr)(toks)?
     } else {
         (Vec::new(), toks)
     };
-    let (body, toks) = assemble_indented_ast(toks)?;
-    Ok((ASTStatement::Class(class_name, children, body), toks))
+    let (body, toks) = assemble_indented_ast(toks, "':' after class name")?;
+    Ok((ASTStatementKind::Class(class_name, children, body), toks))
 }
 
-fn assemble_expr_stmt(toks: &[Token]) -> Result<(ASTStatement, &[Token]), String> {
-    let (expr, toks) = assemble_expr(toks)?;
-    match toks.get(0) {
-        Some(Token::Equals) => {
-            let (rhs, toks) = assemble_expr(&toks[1..])?;
+fn assemble_expr_stmt(toks: &[Token]) -> Assembled<'_, ASTStatementKind> {
+    let (expr, toks) = assemble_expr_list_or_yield(toks)?;
+    match toks[0].kind {
+        TokenKind::Equals => {
+            // `a = b = ... = rhs`
+            let mut targets = vec![expr];
+            let (mut rhs, mut toks) = assemble_expr_list_or_yield(&toks[1..])?;
+            while toks[0].kind == TokenKind::Equals {
+                let (next, toks2) = assemble_expr_list_or_yield(&toks[1..])?;
+                targets.push(std::mem::replace(&mut rhs, next));
+                toks = toks2;
+            }
+            for t in &targets {
+                check_target(t, TargetKind::Assign)?;
+            }
             // TODO this is not actually an expr statement!
-            Ok((ASTStatement::Assign(expr, rhs), toks))
+            Ok((ASTStatementKind::Assign(targets, rhs), toks))
         },
-        Some(Token::AugOp(op)) => {
-            let (rhs, toks) = assemble_expr(&toks[1..])?;
+        TokenKind::AugOp(op) => {
+            let (rhs, toks) = match toks[1].kind {
+                TokenKind::Yield => assemble_yield_expr(&toks[1..])?,
+                _ => assemble_expr(&toks[1..])?,
+            };
+            check_target(&expr, TargetKind::AugAssign)?;
             // TODO this is not actually an expr statement!
-            Ok((ASTStatement::AugAssign(expr, *op, rhs), toks))
+            Ok((ASTStatementKind::AugAssign(expr, op, rhs), toks))
         },
-        _ => Ok((ASTStatement::Expr(expr), toks)),
+        _ => Ok((ASTStatementKind::Expr(expr), toks)),
     }
 }
 
-fn assemble_indented_ast(toks: &[Token]) -> Result<(AST, &[Token]), String> {
-    let ((), toks) = assemble_token(Token::Colon)(toks)?;
-    let ((), toks) = assemble_token(Token::Indent)(toks)?;
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TargetKind { Assign, AugAssign, Del }
+
+// only variables, attributes and subscripts can be assigned to, or tuples and lists of them.
+// augmented assignments don't allow for tuples and lists.
+fn check_target(e: &ASTExpr, kind: TargetKind) -> Result<(), ParseError> {
+    let what = match &e.kind {
+        ASTExprKind::Var(_) | ASTExprKind::Attribute(..) | ASTExprKind::BinOp(ASTBinOpKind::Subscript, ..) => return Ok(()),
+        ASTExprKind::Tuple(elems) | ASTExprKind::List(elems) if kind != TargetKind::AugAssign => {
+            for e in elems {
+                check_target(e, kind)?;
+            }
+            return Ok(());
+        },
+        ASTExprKind::Tuple(_) => "tuple",
+        ASTExprKind::List(_) => "list",
+        ASTExprKind::Str(_) | ASTExprKind::Int(_) | ASTExprKind::Float(_) => "literal",
+        ASTExprKind::Bool(true) => "True",
+        ASTExprKind::Bool(false) => "False",
+        ASTExprKind::None => "None",
+        ASTExprKind::Dict(_) => "dict literal",
+        ASTExprKind::FnCall(..) => "function call",
+        ASTExprKind::BinOp(op, ..) if is_comparison(*op) => "comparison",
+        ASTExprKind::Compare(..) => "comparison",
+        ASTExprKind::BinOp(..) | ASTExprKind::UnOp(..) | ASTExprKind::Slice(_) => "expression",
+        ASTExprKind::IfExpr(..) => "conditional expression",
+        ASTExprKind::Yield(_) | ASTExprKind::YieldFrom(_) => "yield expression",
+    };
+    let msg = match kind {
+        TargetKind::Assign => format!("cannot assign to {what}"),
+        TargetKind::AugAssign => format!("'{what}' is an illegal expression for augmented assignment"),
+        TargetKind::Del => format!("cannot delete {what}"),
+    };
+    Err(ParseError { msg, span: e.span })
+}
+
+// `:` followed by an indented block, or by a single statement on the same line.
+// `colon_what` describes the missing `:` in the error message.
+fn assemble_indented_ast<'a>(toks: &'a [Token], colon_what: &str) -> Assembled<'a, AST> {
+    let ((), toks) = expect_token(toks, TokenKind::Colon, colon_what)?;
+    if toks[0].kind != TokenKind::Newline {
+        let (stmt, toks) = assemble_simple_stmt(toks)?;
+        return Ok((vec![stmt], toks));
+    }
+    let ((), toks) = expect_token(&toks[1..], TokenKind::Indent, "an indented block")?;
     let (body, toks) = assemble_ast(toks)?;
-    let ((), toks) = assemble_token(Token::Unindent)(toks)?;
+    let ((), toks) = expect_token(toks, TokenKind::Unindent, "unindent")?;
 
     Ok((body, toks))
 }
 
-trait Assembler<T>: for<'a> Fn(&[Token]) -> Result<(T, &[Token]), String> {}
-impl<A, T> Assembler<T> for A where A: for<'a> Fn(&[Token]) -> Result<(T, &[Token]), String> {}
-
-fn or<T>(a: impl Assembler<T>, b: impl Assembler<T>) -> impl Assembler<T> {
-    move |toks| a(toks).or_else(|err| b(toks).map_err(|err2| format!("({err})|({err2})")))
-}
+trait Assembler<T>: Fn(&[Token]) -> Result<(T, &[Token]), ParseError> {}
+impl<A, T> Assembler<T> for A where A: Fn(&[Token]) -> Result<(T, &[Token]), ParseError> {}
@@ -6,15 +6,26 @@ pub use tokenize::*;
 mod assemble;
 pub use assemble::*;
 
+mod span;
+pub use span::*;
+
+use std::fmt::{self, Debug, Formatter};
+
 pub type AST = Vec<ASTStatement>;
 
+#[derive(Clone)]
+pub struct ASTStatement {
+    pub kind: ASTStatementKind,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
-pub enum ASTStatement {
-    Assign(ASTExpr, ASTExpr),
+pub enum ASTStatementKind {
+    Assign(/*targets*/ Vec<ASTExpr>, ASTExpr), // `a = b = x` has two targets.
     AugAssign(ASTExpr, ASTAugOpKind, ASTExpr), // https://docs.python.org/3/reference/simple_stmts.html#grammar-token-python-grammar-augmented_assignment_stmt
     Def(
         /*fn name*/ String,
-        /*args*/ Vec<String>,
+        /*params*/ Vec<Param>,
         /*body*/ AST,
     ),
     Class(
@@ -24,65 +35,138 @@ pub enum ASTStatement {
     ),
     If(ASTExpr, AST, /*else*/ Option<AST>),
     While(ASTExpr, AST, /*else*/ Option<AST>),
-    For(/*var*/ String, ASTExpr, AST),
+    For(/*target*/ ASTExpr, ASTExpr, AST, /*else*/ Option<AST>),
     Break,
     Continue,
     Return(Option<ASTExpr>),
     Expr(ASTExpr),
     Pass,
     Scope(ScopeKind, Vec<String>),
-    Try(AST, Vec<Except>),
-    Raise(ASTExpr),
+    Try(AST, Vec<Except>, /*else*/ Option<AST>, /*finally*/ Option<AST>),
+    Raise(Option<ASTExpr>), // None for a bare `raise`, which re-raises.
+    Del(/*targets*/ Vec<ASTExpr>),
 }
 
 #[derive(Debug, Clone)]
 pub struct Except {
     pub ty: Option<ASTExpr>,
+    pub name: Option<String>, // except E as name
     pub body: AST,
 }
 
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamKind,
+    pub default: Option<ASTExpr>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParamKind {
+    Normal, // a or a=default. If it comes after *args, it's keyword-only.
+    VarArgs, // *args
+    VarKwArgs, // **kwargs
+}
+
+#[derive(Debug, Clone)]
+pub enum ASTArg {
+    Positional(ASTExpr), // f(x)
+    Keyword(String, ASTExpr), // f(a=x)
+    Star(ASTExpr), // f(*xs)
+    DoubleStar(ASTExpr), // f(**kw)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ScopeKind {
     Global,
     NonLocal,
 }
 
+#[derive(Clone)]
+pub struct ASTExpr {
+    pub kind: ASTExprKind,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
-pub enum ASTExpr {
+pub enum ASTExprKind {
     Var(String),
     Str(String),
-    Int(i64),
+    Int(BigInt),
+    Float(R64),
     Bool(bool),
     List(Vec<ASTExpr>),
+    Tuple(Vec<ASTExpr>),
+    Dict(Vec<(/*key*/ ASTExpr, /*value*/ ASTExpr)>),
     None,
-    FnCall(Box<ASTExpr>, Vec<ASTExpr>),
+    FnCall(Box<ASTExpr>, Vec<ASTArg>),
     BinOp(ASTBinOpKind, Box<ASTExpr>, Box<ASTExpr>),
     UnOp(ASTUnOpKind, Box<ASTExpr>),
+    IfExpr(/*cond*/ Box<ASTExpr>, /*then*/ Box<ASTExpr>, /*else*/ Box<ASTExpr>), // `then if cond else else`
+    Compare(Box<ASTExpr>, Vec<(ASTBinOpKind, ASTExpr)>), // a chain of at least two comparisons, like `a < b <= c`
     Attribute(Box<ASTExpr>, String),
     Slice(Box<(/*a*/ Option<ASTExpr>, /*b*/ Option<ASTExpr>, /*c*/ Option<ASTExpr>)>), // [a:b:c]
+    Yield(Option<Box<ASTExpr>>),
+    YieldFrom(Box<ASTExpr>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ASTUnOpKind {
     Neg, // -X
+    Not, // not X
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ASTBinOpKind {
-    Plus, Minus, Mul, Div, Mod, Pow,
+    Plus, Minus, Mul, Div, FloorDiv, Mod, Pow,
+    LShift, RShift, BitAnd, BitOr, BitXor,
     Lt, Le, Gt, Ge,
     IsEqual, IsNotEqual,
+    In, NotIn,
+    Is, IsNot,
     Subscript,
 
     And, Or
 }
 
-// TODO add rest:
-// "+=" | "-=" | "*=" | "@=" | "/=" | "//=" | "%=" | "**=" | ">>=" | "<<=" | "&=" | "^=" | "|="
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ASTAugOpKind {
     PlusEq, // +=
     MinusEq, // -=
     MulEq, // *=
+    MatMulEq, // @=
     DivEq, // /=
+    FloorDivEq, // //=
+    ModEq, // %=
+    PowEq, // **=
+    RShiftEq, // >>=
+    LShiftEq, // <<=
+    AndEq, // &=
+    XorEq, // ^=
+    OrEq, // |=
+}
+
+impl ASTStatementKind {
+    pub fn at(self, span: Span) -> ASTStatement {
+        ASTStatement { kind: self, span }
+    }
+}
+
+impl ASTExprKind {
+    pub fn at(self, span: Span) -> ASTExpr {
+        ASTExpr { kind: self, span }
+    }
+}
+
+// The spans are left out, to keep `--show-ast` and the comments in the lowered IR readable.
+impl Debug for ASTStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl Debug for ASTExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
 }
@@ -0,0 +1,65 @@
+use crate::*;
+use std::fmt::{self, Display, Debug, Formatter};
+
+// A range of source code. Lines and columns are 1-based, the end is exclusive.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub file: Symbol,
+    pub line: usize,
+    pub col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    // the smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            end_line: other.end_line,
+            end_col: other.end_col,
+            ..self
+        }
+    }
+}
+
+impl Debug for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub msg: String,
+    pub span: Span,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.msg, self.span.line, self.span.col)
+    }
+}
+
+impl ParseError {
+    // renders the error together with the offending line, e.g.
+    //
+    // error: expected ':' after while condition at 2:12
+    //  --> foo.py:2:12
+    //   |
+    // 2 | while x < 3
+    //   |            ^
+    pub fn render(&self, src: &str) -> String {
+        let Span { file, line, col, end_line, end_col } = self.span;
+        let src_line = src.lines().nth(line - 1).unwrap_or("");
+
+        let carets = if end_line == line && end_col > col { end_col - col } else { 1 };
+        let gutter = " ".repeat(line.to_string().len());
+
+        let mut s = format!("error: {self}\n");
+        s.push_str(&format!("{gutter}--> {file}:{line}:{col}\n"));
+        s.push_str(&format!("{gutter} |\n"));
+        s.push_str(&format!("{line} | {src_line}\n"));
+        s.push_str(&format!("{gutter} | {}{}", " ".repeat(col - 1), "^".repeat(carets)));
+        s
+    }
+}
@@ -1,9 +1,10 @@
 use crate::*;
 
-#[derive(Debug, PartialEq)]
-pub enum Token {
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenKind {
     Ident(String),
-    Int(i64),
+    Int(BigInt),
+    Float(R64),
     Str(String),
     Bool(bool),
     None,
@@ -12,14 +13,19 @@ pub enum Token {
     RParen,
     LBracket,
     RBracket,
+    LBrace,
+    RBrace,
     Comma,
     Dot,
     Equals,
     If,
+    Elif,
     While,
     Else,
     For,
     In,
+    Not,
+    Is,
     Return,
     Break,
     Continue,
@@ -34,36 +40,118 @@ pub enum Token {
     AugOp(ASTAugOpKind),
     Try,
     Except,
-    Raise
+    Finally,
+    Raise,
+    As,
+    Del,
+    Yield,
+    From,
+    Eof,
+}
+
+#[derive(Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+impl std::fmt::Debug for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}@{:?}", self.kind, self.span)
+    }
 }
 
 fn ident_char(c: char) -> bool {
-    ('0'..='9').contains(&c) || ('a'..='z').contains(&c) || ('A'..='Z').contains(&c) || c == '_'
+    c.is_ascii_alphanumeric() || c == '_'
 }
 
 fn int_char(c: char) -> bool {
-    ('0'..='9').contains(&c)
+    c.is_ascii_digit()
+}
+
+fn hex_char(chars: &[char], len: usize) -> Option<char> {
+    let digits: String = chars.get(..len)?.iter().collect();
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) { return None; }
+    u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+}
+
+// decodes the escape sequence after a backslash, returns the decoded string and the number of chars consumed.
+// unknown escapes keep their backslash, like in Python. Malformed hex escapes return None.
+fn unescape(chars: &[char]) -> Option<(String, usize)> {
+    let out = match chars {
+        ['\n', ..] => (String::new(), 1),
+        ['\\', ..] => ("\\".to_string(), 1),
+        ['\'', ..] => ("'".to_string(), 1),
+        ['"', ..] => ("\"".to_string(), 1),
+        ['n', ..] => ("\n".to_string(), 1),
+        ['t', ..] => ("\t".to_string(), 1),
+        ['r', ..] => ("\r".to_string(), 1),
+        ['a', ..] => ("\x07".to_string(), 1),
+        ['b', ..] => ("\x08".to_string(), 1),
+        ['f', ..] => ("\x0c".to_string(), 1),
+        ['v', ..] => ("\x0b".to_string(), 1),
+        ['x', rest@..] => (hex_char(rest, 2)?.to_string(), 3),
+        ['u', rest@..] => (hex_char(rest, 4)?.to_string(), 5),
+        ['U', rest@..] => (hex_char(rest, 8)?.to_string(), 9),
+        [c, ..] if c.is_digit(8) => {
+            let len = chars.iter().take(3).take_while(|c| c.is_digit(8)).count();
+            let digits: String = chars[..len].iter().collect();
+            let c = u32::from_str_radix(&digits, 8).ok().and_then(char::from_u32)?;
+            (c.to_string(), len)
+        },
+        _ => ("\\".to_string(), 0),
+    };
+    Some(out)
 }
 
 enum TokenizerState {
     CountingIndents(usize),
     InLine,
-    InInt(String),
-    InStr(char, String),
-    InIdent(String),
+    InInt(/*start*/ usize, String),
+    InFloat(/*start*/ usize, String),
+    InStr(/*start*/ usize, char, String),
+    InIdent(/*start*/ usize, String),
     InComment, // #
 }
 
-pub fn tokenize(s: &str) -> Vec<Token> {
+pub fn tokenize(s: &str, file: Symbol) -> Result<Vec<Token>, ParseError> {
     let mut chars: Vec<_> = s.chars().collect();
-    chars.push('#'); // automatically closes off final idents.
+    chars.push('\n'); // automatically closes off the final line.
+
+    // (line, column) of each char.
+    let mut positions = Vec::new();
+    let (mut line, mut col) = (1, 1);
+    for c in &chars {
+        positions.push((line, col));
+        if *c == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    positions.push((line, col));
+
+    // the span from chars[start] up to (excluding) chars[end].
+    let span = |start: usize, end: usize| {
+        let (line, col) = positions[start];
+        let (end_line, end_col) = positions[end];
+        Span { file, line, col, end_line, end_col }
+    };
+    let error = |msg: String, start: usize, end: usize| Err(ParseError { msg, span: span(start, end) });
 
     // tells how deeply indented previous indents were.
     // this stack is never empty!
     let mut indent_stack: Vec<usize> = vec![0];
 
+    // how many brackets are currently open. Newlines within brackets are ignored.
+    let mut depth: isize = 0;
+
     let mut i = 0;
     let mut tokens = Vec::new();
+    let push = |tokens: &mut Vec<Token>, kind: TokenKind, start: usize, end: usize| {
+        tokens.push(Token { kind, span: span(start, end) });
+    };
 
     let mut state = TokenizerState::CountingIndents(0);
 
@@ -74,124 +162,219 @@ pub fn tokenize(s: &str) -> Vec<Token> {
                 if c == '\t' {
                     state = TokenizerState::CountingIndents(((n + 8)/8)*8);
                     i += 1;
-                } else if c == ' ' {
+                } else if c == ' ' || c == '\r' {
                     state = TokenizerState::CountingIndents(n + 1);
                     i += 1;
                 } else if c == '\n' {
                     // this ignores empty lines.
                     state = TokenizerState::CountingIndents(0);
                     i += 1;
+                } else if c == '#' {
+                    // lines containing only a comment don't affect indentation.
+                    state = TokenizerState::InComment;
+                    i += 1;
                 } else {
                     state = TokenizerState::InLine;
                     if n > *indent_stack.last().unwrap() {
-                        tokens.push(Token::Indent);
+                        push(&mut tokens, TokenKind::Indent, i, i);
                         indent_stack.push(n);
                     }
                     while n < *indent_stack.last().unwrap() {
                         indent_stack.pop();
-                        tokens.push(Token::Unindent);
+                        push(&mut tokens, TokenKind::Unindent, i, i);
+                    }
+                    if n != *indent_stack.last().unwrap() {
+                        return error("unindent does not match any outer indentation level".to_string(), i, i+1);
                     }
                 }
             }
-            TokenizerState::InLine => match &chars[i..] {
-                ['+', '=', ..] => { tokens.push(Token::AugOp(ASTAugOpKind::PlusEq)); i += 2; }
-                ['-', '=', ..] => { tokens.push(Token::AugOp(ASTAugOpKind::MinusEq)); i += 2; }
-                ['*', '=', ..] => { tokens.push(Token::AugOp(ASTAugOpKind::MulEq)); i += 2; }
-                ['/', '=', ..] => { tokens.push(Token::AugOp(ASTAugOpKind::DivEq)); i += 2; }
-
-                ['+', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Plus)); i += 1; }
-                ['-', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Minus)); i += 1; }
-                ['*', '*', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Pow)); i += 2; }
-                ['*', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Mul)); i += 1; }
-                ['/', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Div)); i += 1; }
-                ['%', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Mod)); i += 1; }
-                ['<', '=', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Le)); i += 2; }
-                ['>', '=', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Ge)); i += 2; }
-                ['=', '=', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::IsEqual)); i += 2; }
-                ['!', '=', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::IsNotEqual)); i += 2; }
-                ['<', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Lt)); i += 1; }
-                ['>', ..] => { tokens.push(Token::BinOp(ASTBinOpKind::Gt)); i += 1; }
-                ['"', ..] => { state = TokenizerState::InStr('"', String::new()); i += 1; }
-                ['\'', ..] => { state = TokenizerState::InStr('\'', String::new()); i += 1; }
-                ['\n', ..] => { state = TokenizerState::CountingIndents(0); i += 1; }
-                ['#', ..] => { state = TokenizerState::InComment; i += 1; }
-                [':', ..] => { tokens.push(Token::Colon); i += 1; }
-                ['(', ..] => { tokens.push(Token::LParen); i += 1; }
-                [')', ..] => { tokens.push(Token::RParen); i += 1; }
-                ['[', ..] => { tokens.push(Token::LBracket); i += 1; }
-                [']', ..] => { tokens.push(Token::RBracket); i += 1; }
-                [',', ..] => { tokens.push(Token::Comma); i += 1; }
-                ['=', ..] => { tokens.push(Token::Equals); i += 1; }
-                ['.', ..] => { tokens.push(Token::Dot); i += 1; }
-
-                [' ', ..] => { i += 1; } _ if int_char(c) => {
-                    state = TokenizerState::InInt(c.to_string());
-                    i += 1;
-                }
-                _ if ident_char(c) => {
-                    state = TokenizerState::InIdent(c.to_string());
-                    i += 1;
+            TokenizerState::InLine => {
+                // a token of `len` chars, starting at `i`.
+                let tok = match &chars[i..] {
+                    ['/', '/', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::FloorDivEq), 3)),
+                    ['*', '*', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::PowEq), 3)),
+                    ['>', '>', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::RShiftEq), 3)),
+                    ['<', '<', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::LShiftEq), 3)),
+                    ['+', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::PlusEq), 2)),
+                    ['-', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::MinusEq), 2)),
+                    ['*', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::MulEq), 2)),
+                    ['@', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::MatMulEq), 2)),
+                    ['/', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::DivEq), 2)),
+                    ['%', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::ModEq), 2)),
+                    ['&', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::AndEq), 2)),
+                    ['^', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::XorEq), 2)),
+                    ['|', '=', ..] => Some((TokenKind::AugOp(ASTAugOpKind::OrEq), 2)),
+
+                    ['+', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Plus), 1)),
+                    ['-', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Minus), 1)),
+                    ['*', '*', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Pow), 2)),
+                    ['*', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Mul), 1)),
+                    ['/', '/', ..] => Some((TokenKind::BinOp(ASTBinOpKind::FloorDiv), 2)),
+                    ['/', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Div), 1)),
+                    ['%', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Mod), 1)),
+                    ['<', '<', ..] => Some((TokenKind::BinOp(ASTBinOpKind::LShift), 2)),
+                    ['>', '>', ..] => Some((TokenKind::BinOp(ASTBinOpKind::RShift), 2)),
+                    ['&', ..] => Some((TokenKind::BinOp(ASTBinOpKind::BitAnd), 1)),
+                    ['|', ..] => Some((TokenKind::BinOp(ASTBinOpKind::BitOr), 1)),
+                    ['^', ..] => Some((TokenKind::BinOp(ASTBinOpKind::BitXor), 1)),
+                    ['<', '=', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Le), 2)),
+                    ['>', '=', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Ge), 2)),
+                    ['=', '=', ..] => Some((TokenKind::BinOp(ASTBinOpKind::IsEqual), 2)),
+                    ['!', '=', ..] => Some((TokenKind::BinOp(ASTBinOpKind::IsNotEqual), 2)),
+                    ['<', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Lt), 1)),
+                    ['>', ..] => Some((TokenKind::BinOp(ASTBinOpKind::Gt), 1)),
+                    ['"', ..] => { state = TokenizerState::InStr(i, '"', String::new()); i += 1; None }
+                    ['\'', ..] => { state = TokenizerState::InStr(i, '\'', String::new()); i += 1; None }
+                    ['\n', ..] if depth > 0 => { i += 1; None }
+                    ['\n', ..] => {
+                        // lines without any tokens don't end a statement.
+                        state = TokenizerState::CountingIndents(0);
+                        if tokens.last().is_some_and(|t| t.kind != TokenKind::Newline) {
+                            Some((TokenKind::Newline, 1))
+                        } else {
+                            i += 1;
+                            None
+                        }
+                    }
+                    ['#', ..] => { state = TokenizerState::InComment; i += 1; None }
+                    [':', ..] => Some((TokenKind::Colon, 1)),
+                    ['(', ..] => { depth += 1; Some((TokenKind::LParen, 1)) }
+                    [')', ..] => { depth -= 1; Some((TokenKind::RParen, 1)) }
+                    ['[', ..] => { depth += 1; Some((TokenKind::LBracket, 1)) }
+                    [']', ..] => { depth -= 1; Some((TokenKind::RBracket, 1)) }
+                    ['{', ..] => { depth += 1; Some((TokenKind::LBrace, 1)) }
+                    ['}', ..] => { depth -= 1; Some((TokenKind::RBrace, 1)) }
+                    [',', ..] => Some((TokenKind::Comma, 1)),
+                    ['=', ..] => Some((TokenKind::Equals, 1)),
+                    ['.', d, ..] if int_char(*d) => {
+                        state = TokenizerState::InFloat(i, c.to_string());
+                        i += 1;
+                        None
+                    }
+                    ['.', ..] => Some((TokenKind::Dot, 1)),
+
+                    [' ' | '\t' | '\r', ..] => { i += 1; None }
+                    _ if int_char(c) => {
+                        state = TokenizerState::InInt(i, c.to_string());
+                        i += 1;
+                        None
+                    }
+                    _ if ident_char(c) => {
+                        state = TokenizerState::InIdent(i, c.to_string());
+                        i += 1;
+                        None
+                    }
+                    _ => return error(format!("unknown character '{c}'"), i, i+1),
+                };
+                if let Some((kind, len)) = tok {
+                    push(&mut tokens, kind, i, i+len);
+                    i += len;
                 }
-                _ => panic!("unknown char '{c}'"),
             },
-            TokenizerState::InStr(delim, mut s) => {
+            TokenizerState::InStr(start, delim, mut s) => {
                 if c == delim {
-                    tokens.push(Token::Str(s));
+                    push(&mut tokens, TokenKind::Str(s), start, i+1);
                     state = TokenizerState::InLine;
+                } else if c == '\n' {
+                    return error("unterminated string literal".to_string(), start, i);
+                } else if c == '\\' {
+                    let Some((x, len)) = unescape(&chars[i+1..]) else {
+                        return error("invalid escape sequence".to_string(), i, i+1);
+                    };
+                    s.push_str(&x);
+                    state = TokenizerState::InStr(start, delim, s);
+                    i += len;
                 } else {
                     s.push(c);
-                    state = TokenizerState::InStr(delim, s);
+                    state = TokenizerState::InStr(start, delim, s);
                 }
                 i += 1;
             }
-            TokenizerState::InIdent(mut s) => {
+            TokenizerState::InIdent(start, mut s) => {
                 if ident_char(c) {
                     s.push(c);
-                    state = TokenizerState::InIdent(s);
+                    state = TokenizerState::InIdent(start, s);
                     i += 1;
                 } else {
-                    tokens.push(match &*s {
-                        "if" => Token::If,
-                        "while" => Token::While,
-                        "else" => Token::Else,
-                        "for" => Token::For,
-                        "in" => Token::In,
-                        "return" => Token::Return,
-                        "break" => Token::Break,
-                        "continue" => Token::Continue,
-                        "def" => Token::Def,
-                        "class" => Token::Class,
-                        "pass" => Token::Pass,
-                        "True" => Token::Bool(true),
-                        "False" => Token::Bool(false),
-                        "None" => Token::None,
-                        "global" => Token::Scope(ScopeKind::Global),
-                        "nonlocal" => Token::Scope(ScopeKind::NonLocal),
-                        "try" => Token::Try,
-                        "except" => Token::Except,
-                        "raise" => Token::Raise,
-                        "and" => Token::BinOp(ASTBinOpKind::And),
-                        "or" => Token::BinOp(ASTBinOpKind::Or),
-                        _ => Token::Ident(s),
-                    });
+                    let kind = match &*s {
+                        "if" => TokenKind::If,
+                        "elif" => TokenKind::Elif,
+                        "while" => TokenKind::While,
+                        "else" => TokenKind::Else,
+                        "for" => TokenKind::For,
+                        "in" => TokenKind::In,
+                        "not" => TokenKind::Not,
+                        "is" => TokenKind::Is,
+                        "del" => TokenKind::Del,
+                        "yield" => TokenKind::Yield,
+                        "from" => TokenKind::From,
+                        "return" => TokenKind::Return,
+                        "break" => TokenKind::Break,
+                        "continue" => TokenKind::Continue,
+                        "def" => TokenKind::Def,
+                        "class" => TokenKind::Class,
+                        "pass" => TokenKind::Pass,
+                        "True" => TokenKind::Bool(true),
+                        "False" => TokenKind::Bool(false),
+                        "None" => TokenKind::None,
+                        "global" => TokenKind::Scope(ScopeKind::Global),
+                        "nonlocal" => TokenKind::Scope(ScopeKind::NonLocal),
+                        "try" => TokenKind::Try,
+                        "except" => TokenKind::Except,
+                        "finally" => TokenKind::Finally,
+                        "raise" => TokenKind::Raise,
+                        "as" => TokenKind::As,
+                        "and" => TokenKind::BinOp(ASTBinOpKind::And),
+                        "or" => TokenKind::BinOp(ASTBinOpKind::Or),
+                        _ => TokenKind::Ident(s),
+                    };
+                    push(&mut tokens, kind, start, i);
                     state = TokenizerState::InLine;
                 }
             }
-            TokenizerState::InInt(mut s) => {
+            TokenizerState::InInt(start, mut s) => {
                 if int_char(c) {
                     s.push(c);
-                    state = TokenizerState::InInt(s);
+                    state = TokenizerState::InInt(start, s);
+                    i += 1;
+                } else if matches!(c, '.' | 'e' | 'E') {
+                    s.push(c);
+                    state = TokenizerState::InFloat(start, s);
+                    i += 1;
+                } else {
+                    let Ok(int) = s.parse() else {
+                        return error(format!("can't parse integer literal '{s}'"), start, i);
+                    };
+                    push(&mut tokens, TokenKind::Int(int), start, i);
+                    state = TokenizerState::InLine;
+                }
+            }
+            TokenizerState::InFloat(start, mut s) => {
+                let in_exp = s.contains(['e', 'E']);
+                let cont = int_char(c)
+                    || (c == '.' && !in_exp && !s.contains('.'))
+                    || (matches!(c, 'e' | 'E') && !in_exp)
+                    || (matches!(c, '+' | '-') && s.ends_with(['e', 'E']));
+                if cont {
+                    s.push(c);
+                    state = TokenizerState::InFloat(start, s);
                     i += 1;
                 } else {
-                    let int = s.parse().expect("Can't parse int!");
-                    tokens.push(Token::Int(int));
+                    let Ok(x) = s.parse::<f64>() else {
+                        return error(format!("can't parse float literal '{s}'"), start, i);
+                    };
+                    // infinite floats are not supported.
+                    let Some(x) = finite(x) else {
+                        return error(format!("float literal '{s}' is infinite, which is not supported"), start, i);
+                    };
+                    push(&mut tokens, TokenKind::Float(x), start, i);
                     state = TokenizerState::InLine;
                 }
             }
             TokenizerState::InComment => {
                 if c == '\n' {
-                    state = TokenizerState::CountingIndents(0);
-                    i += 1;
+                    // the newline is handled by the InLine state.
+                    state = TokenizerState::InLine;
                 } else {
                     i += 1;
                 }
@@ -199,5 +382,16 @@ pub fn tokenize(s: &str) -> Vec<Token> {
         }
     }
 
-    tokens
+    // close all remaining blocks.
+    // errors at the end of the file point right after its last non-whitespace character.
+    let mut end = chars.len() - 1;
+    while end > 0 && chars[end - 1].is_whitespace() {
+        end -= 1;
+    }
+    for _ in 1..indent_stack.len() {
+        push(&mut tokens, TokenKind::Unindent, end, end);
+    }
+    push(&mut tokens, TokenKind::Eof, end, end);
+
+    Ok(tokens)
 }
@@ -0,0 +1,93 @@
+use crate::ir::*;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{FromPrimitive, One};
+use std::cmp::Ordering;
+
+// Division and modulo with python semantics, i.e. rounding towards negative infinity.
+// The result of `py_mod` has the sign of the divisor.
+// The divisor must not be zero.
+
+pub fn py_floordiv(l: &BigInt, r: &BigInt) -> BigInt {
+    l.div_floor(r)
+}
+
+pub fn py_mod(l: &BigInt, r: &BigInt) -> BigInt {
+    l.mod_floor(r)
+}
+
+// shifts of i64s with python semantics, for the analyses. The shift count must not be negative.
+// `py_shl` returns None, if the result doesn't fit into an i64.
+pub fn py_shl(l: i64, r: i64) -> Option<i64> {
+    let x = l.checked_shl(u32::try_from(r).ok()?)?;
+    (x >> r == l).then_some(x)
+}
+
+pub fn py_shr(l: i64, r: i64) -> i64 {
+    l >> r.min(63)
+}
+
+// like python, the remainder takes the sign of the divisor, even if it's zero.
+pub fn py_fmod(l: R64, r: R64) -> R64 {
+    let m = l % r;
+    if m == 0.0 {
+        R64::new(0.0f64.copysign(r.raw()))
+    } else if (m < 0.0) != (r < 0.0) {
+        m + r
+    } else {
+        m
+    }
+}
+
+// floats are always finite, infinite or NaN results give None.
+pub fn finite(x: f64) -> Option<R64> {
+    x.is_finite().then(|| R64::new(x))
+}
+
+// ints that are too large for a float give None, python raises an OverflowError for them.
+pub fn int_to_float(x: &BigInt) -> Option<R64> {
+    finite(x.to_f64()?)
+}
+
+// compares an int with a finite float exactly, like python.
+// The int is compared with the integral part of the float first, and then the fraction decides.
+pub fn cmp_int_float(l: &BigInt, r: R64) -> Ordering {
+    let floor = r.raw().floor();
+    let floor_int = BigInt::from_f64(floor).expect("floats are finite");
+    match l.cmp(&floor_int) {
+        Ordering::Equal if r.raw() > floor => Ordering::Less,
+        o => o,
+    }
+}
+
+// whether the comparison `kind` holds for operands that compare as `o`.
+pub fn cmp_holds(kind: BinOpKind, o: Ordering) -> bool {
+    match kind {
+        BinOpKind::Lt => o.is_lt(),
+        BinOpKind::Le => o.is_le(),
+        BinOpKind::Gt => o.is_gt(),
+        BinOpKind::Ge => o.is_ge(),
+        _ => panic!("not a comparison: {kind}"),
+    }
+}
+
+// python's `l / r` of two ints. The quotient is exact until the final rounding, so that `10**400 / 10**399` is 10.0.
+// Quotients that are too large for a float give None. The divisor must not be zero.
+pub fn py_truediv(l: &BigInt, r: &BigInt) -> Option<R64> {
+    // small ints are exact as floats, then the float division rounds correctly.
+    const EXACT: u64 = 53;
+    if l.bits() <= EXACT && r.bits() <= EXACT {
+        return finite(l.to_f64()? / r.to_f64()?);
+    }
+
+    // scales the quotient to at least 55 significant bits, the rest only matters for rounding and is kept as a sticky bit.
+    let shift = 55 + r.bits() as i64 - l.bits() as i64;
+    let (n, d) = (l.magnitude().clone(), r.magnitude().clone());
+    let (n, d) = if shift >= 0 { (n << shift as usize, d) } else { (n, d << (-shift) as usize) };
+    let (q, rem) = n.div_rem(&d);
+    let q = if rem.is_zero() { q } else { q | BigUint::one() };
+
+    let x = q.to_f64()? * 2f64.powi(-shift.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+    let x = if l.is_negative() != r.is_negative() { -x } else { x };
+    finite(x)
+}
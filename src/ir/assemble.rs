@@ -45,6 +45,8 @@ fn assemble_stmt(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRTok
     let a = assemble_stmt_let;
     let a = or(a, assemble_stmt_store);
     let a = or(a, assemble_stmt_print);
+    let a = or(a, assemble_stmt_eprint);
+    let a = or(a, assemble_stmt_write);
     let a = or(a, assemble_stmt_jmp);
     let a = or(a, assemble_stmt_exit);
     let a = or(a, assemble_stmt_fail);
@@ -78,6 +80,18 @@ fn assemble_stmt_print(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &
     Some((Statement::Print(node), prev, toks))
 }
 
+fn assemble_stmt_eprint(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRToken])> {
+    let [IRToken::Eprint, toks@..] = toks else { return None };
+    let (node, prev, toks) = assemble_to_node(toks)?;
+    Some((Statement::Eprint(node), prev, toks))
+}
+
+fn assemble_stmt_write(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRToken])> {
+    let [IRToken::Write, toks@..] = toks else { return None };
+    let (node, prev, toks) = assemble_to_node(toks)?;
+    Some((Statement::Write(node), prev, toks))
+}
+
 fn assemble_stmt_jmp(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRToken])> {
     let [IRToken::Jmp, toks@..] = toks else { return None };
     let (node, prev, toks) = assemble_to_node(toks)?;
@@ -86,7 +100,11 @@ fn assemble_stmt_jmp(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[I
 
 fn assemble_stmt_exit(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRToken])> {
     let [IRToken::Exit, toks@..] = toks else { return None };
-    Some((Statement::Exit, Vec::new(), toks))
+    if let [IRToken::Semicolon, ..] = toks {
+        return Some((Statement::Exit(None), Vec::new(), toks));
+    }
+    let (node, prev, toks) = assemble_to_node(toks)?;
+    Some((Statement::Exit(Some(node)), prev, toks))
 }
 
 fn assemble_stmt_fail(toks: &[IRToken]) -> Option<(Statement, Vec<Statement>, &[IRToken])> {
@@ -146,7 +164,8 @@ fn assemble_atomic_expr_or_node(toks: &[IRToken]) -> Option<(ExprOrNode, Vec<Sta
         [IRToken::At, toks@..] => Some((ExprOrNode::Expr(Expr::Root), Vec::new(), toks)),
         [IRToken::BinOp(BinOpKind::Mod), IRToken::Symbol(s), toks@..] => Some((ExprOrNode::Node(Node(*s)), Vec::new(), toks)),
         [IRToken::Symbol(s), toks@..] => Some((ExprOrNode::Expr(Expr::Symbol(*s)), Vec::new(), toks)),
-        [IRToken::Int(i), toks@..] => Some((ExprOrNode::Expr(Expr::Int(*i)), Vec::new(), toks)),
+        [IRToken::Int(i), toks@..] => Some((ExprOrNode::Expr(Expr::Int(i.clone())), Vec::new(), toks)),
+        [IRToken::Float(x), toks@..] => Some((ExprOrNode::Expr(Expr::Float(*x)), Vec::new(), toks)),
         [IRToken::Str(s), toks@..] => Some((ExprOrNode::Expr(Expr::Str(s.to_string())), Vec::new(), toks)),
         [IRToken::LBrace, IRToken::RBrace, toks@..] => Some((ExprOrNode::Expr(Expr::NewTable), Vec::new(), toks)),
         [IRToken::LParen, toks@..] => {
@@ -155,6 +174,50 @@ fn assemble_atomic_expr_or_node(toks: &[IRToken]) -> Option<(ExprOrNode, Vec<Sta
             Some((expr_or_node, prev, toks))
         },
         [IRToken::Input, toks@..] => Some((ExprOrNode::Expr(Expr::Input), Vec::new(), toks)),
+        [IRToken::Repr, IRToken::LParen, toks@..] => {
+            let (n, prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::Repr(n)), prev, toks))
+        },
+        [IRToken::StrLen, IRToken::LParen, toks@..] => {
+            let (n, prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::StrLen(n)), prev, toks))
+        },
+        [IRToken::Substr, IRToken::LParen, toks@..] => {
+            let (s, mut prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::Comma, toks@..] = toks else { return None };
+            let (i, prev2, toks) = assemble_to_node(toks)?;
+            prev.extend(prev2);
+            let [IRToken::Comma, toks@..] = toks else { return None };
+            let (j, prev2, toks) = assemble_to_node(toks)?;
+            prev.extend(prev2);
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::Substr(s, i, j)), prev, toks))
+        },
+        [IRToken::ParseInt, IRToken::LParen, toks@..] => {
+            let (n, prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::ParseInt(n)), prev, toks))
+        },
+        [IRToken::ParseFloat, IRToken::LParen, toks@..] => {
+            let (n, prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::ParseFloat(n)), prev, toks))
+        },
+        [IRToken::TrueDiv, IRToken::LParen, toks@..] => {
+            let (l, mut prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::Comma, toks@..] = toks else { return None };
+            let (r, prev2, toks) = assemble_to_node(toks)?;
+            prev.extend(prev2);
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::TrueDiv(l, r)), prev, toks))
+        },
+        [IRToken::Trunc, IRToken::LParen, toks@..] => {
+            let (n, prev, toks) = assemble_to_node(toks)?;
+            let [IRToken::RParen, toks@..] = toks else { return None };
+            Some((ExprOrNode::Expr(Expr::Trunc(n)), prev, toks))
+        },
         _ => None,
     }
 }
@@ -1,4 +1,5 @@
 use crate::ir::*;
+use std::io::Write as _;
 
 type TablePtr = usize;
 
@@ -27,7 +28,7 @@ enum Value {
     TablePtr(TablePtr),
     Str(String),
     Float(R64),
-    Int(i64),
+    Int(BigInt),
     Symbol(Symbol),
 }
 
@@ -72,7 +73,7 @@ fn exec_expr(expr: &Expr, ctxt: &mut Ctxt) -> Value {
         }
         Expr::Symbol(s) => Value::Symbol(*s),
         Expr::Float(x) => Value::Float(*x),
-        Expr::Int(x) => Value::Int(*x),
+        Expr::Int(x) => Value::Int(x.clone()),
         Expr::Str(s) => Value::Str(s.clone()),
         Expr::Input => {
             let stdin = std::io::stdin();
@@ -82,6 +83,37 @@ fn exec_expr(expr: &Expr, ctxt: &mut Ctxt) -> Value {
 
             Value::Str(s)
         }
+        Expr::Repr(n) => match &ctxt.nodes[n] {
+            Value::Int(x) => Value::Str(x.to_string()),
+            Value::Float(x) => Value::Str(py_float_repr(*x)),
+            Value::Str(s) => Value::Str(py_str_repr(s)),
+            v => crash(&format!("repr of non-primitive value {v:?}!"), ctxt),
+        },
+        Expr::StrLen(n) => match &ctxt.nodes[n] {
+            Value::Str(s) => Value::Int(s.chars().count().into()),
+            v => crash(&format!("strlen of non-string value {v:?}!"), ctxt),
+        },
+        Expr::Substr(s, i, j) => match (&ctxt.nodes[s], &ctxt.nodes[i], &ctxt.nodes[j]) {
+            (Value::Str(s), Value::Int(i), Value::Int(j)) => Value::Str(py_substr(s, i, j)),
+            (s, i, j) => crash(&format!("invalid substr({s:?}, {i:?}, {j:?})!"), ctxt),
+        },
+        Expr::ParseInt(n) => match &ctxt.nodes[n] {
+            Value::Str(s) => py_parse_int(s).map_or(ctxt.undef_v.clone(), Value::Int),
+            v => crash(&format!("parse_int of non-string value {v:?}!"), ctxt),
+        },
+        Expr::ParseFloat(n) => match &ctxt.nodes[n] {
+            Value::Str(s) => py_parse_float(s).map_or(ctxt.undef_v.clone(), Value::Float),
+            v => crash(&format!("parse_float of non-string value {v:?}!"), ctxt),
+        },
+        Expr::TrueDiv(l, r) => match (&ctxt.nodes[l], &ctxt.nodes[r]) {
+            (Value::Int(_), Value::Int(r)) if r.is_zero() => crash("division by zero!", ctxt),
+            (Value::Int(l), Value::Int(r)) => py_truediv(l, r).map_or(ctxt.undef_v.clone(), Value::Float),
+            (l, r) => crash(&format!("invalid truediv({l:?}, {r:?})!"), ctxt),
+        },
+        Expr::Trunc(n) => match &ctxt.nodes[n] {
+            Value::Float(x) => Value::Int(py_trunc(*x)),
+            v => crash(&format!("trunc of non-float value {v:?}!"), ctxt),
+        },
     }
 }
 
@@ -94,35 +126,82 @@ fn exec_binop(kind: BinOpKind, l: Value, r: Value, ctxt: &mut Ctxt) -> Value {
     };
 
     match (kind, l, r) {
+        (Div | Mod, _, Value::Int(r)) if r.is_zero() => crash("division by zero!", ctxt),
+        (Div | Mod, _, Value::Float(r)) if r == 0.0 => crash("division by zero!", ctxt),
+
         // int
         (Plus, Value::Int(l), Value::Int(r)) => Value::Int(l + r),
         (Minus, Value::Int(l), Value::Int(r)) => Value::Int(l - r),
         (Mul, Value::Int(l), Value::Int(r)) => Value::Int(l * r),
-        (Div, Value::Int(l), Value::Int(r)) => Value::Int(l / r),
-        (Mod, Value::Int(l), Value::Int(r)) => Value::Int(l % r),
-        (Pow, Value::Int(l), Value::Int(r)) => Value::Int(l.pow(r as _)),
+        (Div, Value::Int(l), Value::Int(r)) => Value::Int(py_floordiv(&l, &r)),
+        (Mod, Value::Int(l), Value::Int(r)) => Value::Int(py_mod(&l, &r)),
+        // like in python, negative exponents give a float.
+        (Pow, Value::Int(l), Value::Int(r)) if r.is_negative() => match int_to_float(&l) {
+            Some(l) => exec_binop(Pow, Value::Float(l), Value::Int(r), ctxt),
+            None => ctxt.undef_v.clone(),
+        },
+        (Pow, Value::Int(l), Value::Int(r)) => match r.to_u32() {
+            Some(r) => Value::Int(l.pow(r)),
+            None => crash("exponent too large!", ctxt),
+        },
+        // shifting by a negative count is a ValueError in python, the callers check for it.
+        (Shl | Shr, Value::Int(_), Value::Int(r)) if r.is_negative() => crash("negative shift count!", ctxt),
+        (Shl, Value::Int(l), Value::Int(r)) => match r.to_usize() {
+            Some(r) => Value::Int(l << r),
+            None => crash("shift count too large!", ctxt),
+        },
+        // rounds towards negative infinity, so shifting out all bits leaves 0 or -1.
+        (Shr, Value::Int(l), Value::Int(r)) => match r.to_usize() {
+            Some(r) => Value::Int(l >> r),
+            None => Value::Int(if l.is_negative() { BigInt::from(-1) } else { BigInt::zero() }),
+        },
+        (BitAnd, Value::Int(l), Value::Int(r)) => Value::Int(l & r),
+        (BitOr, Value::Int(l), Value::Int(r)) => Value::Int(l | r),
+        (BitXor, Value::Int(l), Value::Int(r)) => Value::Int(l ^ r),
         (Lt, Value::Int(l), Value::Int(r)) => boolify(l < r),
         (Le, Value::Int(l), Value::Int(r)) => boolify(l <= r),
         (Gt, Value::Int(l), Value::Int(r)) => boolify(l > r),
         (Ge, Value::Int(l), Value::Int(r)) => boolify(l >= r),
 
-        // float
-        (Plus, Value::Float(l), Value::Float(r)) => Value::Float(l + r),
-        (Minus, Value::Float(l), Value::Float(r)) => Value::Float(l - r),
-        (Mul, Value::Float(l), Value::Float(r)) => Value::Float(l * r),
-        (Div, Value::Float(l), Value::Float(r)) => Value::Float(l / r),
-        (Mod, Value::Float(l), Value::Float(r)) => Value::Float(l % r),
-        (Pow, Value::Float(l), Value::Float(r)) => Value::Float(l.powf(r)),
+        // float, results that aren't finite are Undef.
+        (Plus, Value::Float(l), Value::Float(r)) => float_v(l.raw() + r.raw(), ctxt),
+        (Minus, Value::Float(l), Value::Float(r)) => float_v(l.raw() - r.raw(), ctxt),
+        (Mul, Value::Float(l), Value::Float(r)) => float_v(l.raw() * r.raw(), ctxt),
+        (Div, Value::Float(l), Value::Float(r)) => float_v(l.raw() / r.raw(), ctxt),
+        (Mod, Value::Float(l), Value::Float(r)) => Value::Float(py_fmod(l, r)),
+        (Pow, Value::Float(l), Value::Float(r)) => float_v(l.raw().powf(r.raw()), ctxt),
         (Lt, Value::Float(l), Value::Float(r)) => boolify(l < r),
         (Le, Value::Float(l), Value::Float(r)) => boolify(l <= r),
         (Gt, Value::Float(l), Value::Float(r)) => boolify(l > r),
         (Ge, Value::Float(l), Value::Float(r)) => boolify(l >= r),
 
+        // int and float mixed, comparisons are exact.
+        (Lt | Le | Gt | Ge, Value::Int(l), Value::Float(r)) => boolify(cmp_holds(kind, cmp_int_float(&l, r))),
+        (Lt | Le | Gt | Ge, Value::Float(l), Value::Int(r)) => boolify(cmp_holds(kind, cmp_int_float(&r, l).reverse())),
+        // otherwise the int gets converted, ints that are too large for a float give Undef.
+        (kind, Value::Int(l), Value::Float(r)) => match int_to_float(&l) {
+            Some(l) => exec_binop(kind, Value::Float(l), Value::Float(r), ctxt),
+            None => ctxt.undef_v.clone(),
+        },
+        (kind, Value::Float(l), Value::Int(r)) => match int_to_float(&r) {
+            Some(r) => exec_binop(kind, Value::Float(l), Value::Float(r), ctxt),
+            None => ctxt.undef_v.clone(),
+        },
+
         (Plus, Value::Str(l), Value::Str(r)) => Value::Str(format!("{}{}", l, r)),
+        // strings are compared by their code points, like in python.
+        (Lt, Value::Str(l), Value::Str(r)) => boolify(l < r),
+        (Le, Value::Str(l), Value::Str(r)) => boolify(l <= r),
+        (Gt, Value::Str(l), Value::Str(r)) => boolify(l > r),
+        (Ge, Value::Str(l), Value::Str(r)) => boolify(l >= r),
         (kind, l, r) => crash(&format!("type error! \"{l:?} {kind} {r:?}\""), ctxt),
     }
 }
 
+fn float_v(x: f64, ctxt: &Ctxt) -> Value {
+    finite(x).map_or(ctxt.undef_v.clone(), Value::Float)
+}
+
 fn alloc_table(ctxt: &mut Ctxt) -> Value {
     let tid = ctxt.heap.len();
     ctxt.heap.push(Default::default());
@@ -176,10 +255,32 @@ fn step_stmt(stmt: &Statement, ctxt: &mut Ctxt) -> bool {
                 Value::Symbol(s) => println!("{s}"),
                 Value::Str(s) => println!("{}", s),
                 Value::TablePtr(ptr) => println!("table: {}", ptr),
-                Value::Float(x) => println!("{}", x),
+                Value::Float(x) => println!("{}", py_float_repr(*x)),
                 Value::Int(x) => println!("{}", x),
             }
         }
+        Eprint(n) => {
+            let val = &ctxt.nodes[n];
+            match val {
+                Value::Symbol(s) => eprintln!("{s}"),
+                Value::Str(s) => eprintln!("{}", s),
+                Value::TablePtr(ptr) => eprintln!("table: {}", ptr),
+                Value::Float(x) => eprintln!("{}", py_float_repr(*x)),
+                Value::Int(x) => eprintln!("{}", x),
+            }
+        }
+        Write(n) => {
+            let val = &ctxt.nodes[n];
+            match val {
+                Value::Symbol(s) => print!("{s}"),
+                Value::Str(s) => print!("{}", s),
+                Value::TablePtr(ptr) => print!("table: {}", ptr),
+                Value::Float(x) => print!("{}", py_float_repr(*x)),
+                Value::Int(x) => print!("{}", x),
+            }
+            // stdout is line-buffered, and `exit` doesn't flush it.
+            std::io::stdout().flush().unwrap();
+        }
         Jmp(n) => {
             match ctxt.nodes[n].clone() {
                 Value::Symbol(pid) => {
@@ -190,7 +291,16 @@ fn step_stmt(stmt: &Statement, ctxt: &mut Ctxt) -> bool {
                 v => crash(&format!("trying to execute non-function value! {:?}", v), ctxt),
             };
         }
-        Exit => return false,
+        Exit(None) => return false,
+        Exit(Some(n)) => {
+            let Value::Int(code) = &ctxt.nodes[n] else {
+                crash("exit code is not an int!", ctxt)
+            };
+            let Some(code) = code.to_i32() else {
+                crash("exit code out of range!", ctxt)
+            };
+            std::process::exit(code);
+        }
         Panic(n) => {
             let v = ctxt.nodes[n].clone();
             println!("PANIC: {v:?}");
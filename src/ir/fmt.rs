@@ -43,11 +43,23 @@ fn display_stmt(stmt_id: usize, proc: &Proc, force_visible: bool, f: &mut Format
             let v = node_string(*v, proc);
             write!(f, "    print {v};\n")?;
         },
+        Eprint(v) => {
+            let v = node_string(*v, proc);
+            writeln!(f, "    eprint {v};")?;
+        },
+        Write(v) => {
+            let v = node_string(*v, proc);
+            writeln!(f, "    write {v};")?;
+        },
         Jmp(n) => {
             let n = node_string(*n, proc);
             write!(f, "    jmp {n};\n")?;
         },
-        Exit => write!(f, "    exit;\n")?,
+        Exit(None) => write!(f, "    exit;\n")?,
+        Exit(Some(n)) => {
+            let n = node_string(*n, proc);
+            writeln!(f, "    exit {n};")?;
+        },
         Panic(n) => {
             let n = node_string(*n, proc);
             write!(f, "    panic {n};\n")?;
@@ -70,10 +82,41 @@ fn expr_string(expr: &Expr, proc: &Proc) -> String {
             format!("{l} {kind} {r}")
         }
         Symbol(s) => format!("{s}"),
-        Float(x) => format!("{x}"),
+        Float(x) => fmt_float_literal(*x),
         Int(x) => format!("{x}"),
-        Str(s) => format!("\"{s}\""),
+        Str(s) => fmt_str_literal(s),
         Input => format!("\"input\""),
+        Repr(n) => {
+            let n = node_string(*n, proc);
+            format!("repr({n})")
+        },
+        StrLen(n) => {
+            let n = node_string(*n, proc);
+            format!("strlen({n})")
+        },
+        Substr(s, i, j) => {
+            let s = node_string(*s, proc);
+            let i = node_string(*i, proc);
+            let j = node_string(*j, proc);
+            format!("substr({s}, {i}, {j})")
+        },
+        ParseInt(n) => {
+            let n = node_string(*n, proc);
+            format!("parse_int({n})")
+        },
+        ParseFloat(n) => {
+            let n = node_string(*n, proc);
+            format!("parse_float({n})")
+        },
+        Trunc(n) => {
+            let n = node_string(*n, proc);
+            format!("trunc({n})")
+        },
+        TrueDiv(l, r) => {
+            let l = node_string(*l, proc);
+            let r = node_string(*r, proc);
+            format!("truediv({l}, {r})")
+        },
     }
 }
 
@@ -90,12 +133,40 @@ impl Display for BinOpKind {
             Le => "<=",
             Gt => ">",
             Ge => ">=",
-            Pow => "^",
+            Pow => "**",
+            Shl => "<<",
+            Shr => ">>",
+            BitAnd => "&",
+            BitOr => "|",
+            BitXor => "^",
         };
         write!(f, "{}", s)
     }
 }
 
+// escapes the string, so that it can be tokenized again, see `unescape`.
+pub fn fmt_str_literal(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// floats always contain a '.' or an exponent, so that they don't get tokenized as ints.
+pub fn fmt_float_literal(x: R64) -> String {
+    format!("{:?}", x.raw())
+}
+
 fn node_string(n: Node, proc: &Proc) -> String {
     let (expr, b) = get_def(n, proc);
     if !b {
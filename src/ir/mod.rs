@@ -14,11 +14,24 @@ pub use assemble::*;
 mod exec;
 pub use exec::*;
 
+mod arith;
+pub use arith::*;
+
+mod repr;
+pub use repr::*;
+
+mod string;
+pub use string::*;
+
 pub type Stmt = (Symbol, /*idx*/ usize);
 
+// Div on ints and Mod behave like python's `//` and `%`, see arith.rs.
+// Div on floats is true division.
+// Lt, Le, Gt and Ge also compare strings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BinOpKind {
     Plus, Minus, Mul, Div, Mod, Pow,
+    Shl, Shr, BitAnd, BitOr, BitXor,
     Lt, Le, Gt, Ge,
 }
 
@@ -30,10 +43,12 @@ pub enum Statement {
     Let(Node, Expr, /*visible*/ bool), // create a new node with the value returned from the Expr.
     Store(/*table: */ Node, /*index: */ Node, Node), // store the value from the Node in the table `table` at index `index`.
     Print(Node),
+    Eprint(Node), // like Print, but to stderr.
+    Write(Node), // like Print, but without the trailing newline.
     Jmp(/*proc-id*/ Node),
     Panic(/*err msg or code*/ Node),
     Fail, // analysis tries to prove that this is never reached.
-    Exit,
+    Exit(/*exit code, 0 if missing*/ Option<Node>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -42,14 +57,21 @@ pub enum Expr {
 
     Root, // @
     NewTable, // equivalent to {}
-    BinOp(BinOpKind, Node, Node),
+    BinOp(BinOpKind, Node, Node), // float results that aren't finite are Undef.
 
     Input, // corresponds to pythons "input()" function applied to no args.
+    Repr(Node), // pythons repr() of an int, float or string.
+    StrLen(Node), // the number of characters in a string.
+    Substr(/*string: */ Node, /*start: */ Node, /*end: */ Node), // the characters from start up to end, both indices are clamped to the string.
+    ParseInt(Node), // pythons int() of a string, or Undef if it's not a valid int.
+    ParseFloat(Node), // pythons float() of a string, or Undef if it's not a valid finite float.
+    Trunc(Node), // pythons int() of a float.
+    TrueDiv(Node, Node), // pythons `/` of two ints, or Undef if the quotient is too large for a float.
 
     // literals
     Symbol(Symbol), // $symbol
     Float(R64),
-    Int(i64),
+    Int(BigInt),
     Str(String),
 }
 
@@ -0,0 +1,33 @@
+use crate::ir::*;
+
+// formats floats like python's repr, e.g. `1.0`, `1e+16` or `1.5e-05`.
+pub fn py_float_repr(x: R64) -> String {
+    let s = fmt_float_literal(x);
+    let Some((mantissa, exp)) = s.split_once('e') else { return s };
+    let (sign, digits) = match exp.strip_prefix('-') {
+        Some(digits) => ('-', digits),
+        None => ('+', exp),
+    };
+    format!("{mantissa}e{sign}{digits:0>2}")
+}
+
+// formats strings like python's repr, e.g. `'abc'` or `"it's"`.
+// Like in python, double quotes are only used if that avoids escaping.
+pub fn py_str_repr(s: &str) -> String {
+    let quote = if s.contains('\'') && !s.contains('"') { '"' } else { '\'' };
+
+    let mut out = String::from(quote);
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => { out.push('\\'); out.push(c); },
+            c if c < ' ' || ('\x7f'..='\u{a0}').contains(&c) => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+    out
+}
@@ -0,0 +1,49 @@
+use crate::ir::*;
+use num_traits::FromPrimitive;
+
+// the characters from index `i` up to `j`, where both indices are clamped to the string.
+pub fn py_substr(s: &str, i: &BigInt, j: &BigInt) -> String {
+    let len = s.chars().count();
+    let clamp = |x: &BigInt| if x.is_negative() { 0 } else { x.to_usize().unwrap_or(len).min(len) };
+    let (i, j) = (clamp(i), clamp(j));
+    s.chars().skip(i).take(j.saturating_sub(i)).collect()
+}
+
+// parses an int like python's `int(s)`, e.g. ` -1_000 `.
+// returns None, if python would raise a ValueError.
+pub fn py_parse_int(s: &str) -> Option<BigInt> {
+    let s = s.trim();
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s.strip_prefix('+').unwrap_or(s)),
+    };
+    // underscores are only allowed between digits.
+    if digits.split('_').any(|x| x.is_empty() || !x.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    format!("{sign}{}", digits.replace('_', "")).parse().ok()
+}
+
+// parses a float like python's `float(s)`, e.g. ` -1_0.5e3 `.
+// returns None, if python would raise a ValueError, or if the float isn't finite.
+pub fn py_parse_float(s: &str) -> Option<R64> {
+    let s = s.trim();
+    let chars: Vec<char> = s.chars().collect();
+    // underscores are only allowed between digits.
+    for (i, c) in chars.iter().enumerate() {
+        let digit = |j: Option<usize>| j.and_then(|j| chars.get(j)).is_some_and(|c| c.is_ascii_digit());
+        if *c == '_' && !(digit(i.checked_sub(1)) && digit(Some(i + 1))) {
+            return None;
+        }
+        if !(c.is_ascii_digit() || "_.eE+-".contains(*c)) {
+            return None;
+        }
+    }
+    let x: f64 = s.replace('_', "").parse().ok()?;
+    finite(x)
+}
+
+// python's `int(x)` of a float, which rounds towards zero.
+pub fn py_trunc(x: R64) -> BigInt {
+    BigInt::from_f64(x.raw().trunc()).unwrap()
+}
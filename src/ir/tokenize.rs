@@ -3,7 +3,8 @@ use crate::*;
 #[derive(Debug, PartialEq)]
 pub enum IRToken {
     Symbol(Symbol),
-    Int(i64),
+    Int(BigInt),
+    Float(R64),
     Str(String),
 
     LParen, RParen,
@@ -11,7 +12,7 @@ pub enum IRToken {
     LBrace, RBrace,
     Comma, Dot, Equals, Semicolon, At,
 
-    Proc, Exit, Panic, Jmp, Main, Print, Input, Fail,
+    Proc, Exit, Panic, Jmp, Main, Print, Eprint, Write, Input, Repr, StrLen, Substr, ParseInt, ParseFloat, Trunc, TrueDiv, Fail,
     BinOp(BinOpKind),
 }
 
@@ -26,11 +27,28 @@ fn int_char(c: char) -> bool {
 enum TokenizerState {
     None,
     InInt(String),
+    InFloat(String),
     InStr(char, String),
     InIdent(String),
     InComment, // #
 }
 
+// the escape sequences of python, which follow a backslash.
+// Returns the escaped string, and how many chars after the backslash were consumed.
+// Like in python, unknown escape sequences are kept as they are.
+fn unescape(chars: &[char]) -> (String, usize) {
+    match chars {
+        ['\\', ..] => ("\\".to_string(), 1),
+        ['\'', ..] => ("'".to_string(), 1),
+        ['"', ..] => ("\"".to_string(), 1),
+        ['n', ..] => ("\n".to_string(), 1),
+        ['t', ..] => ("\t".to_string(), 1),
+        ['r', ..] => ("\r".to_string(), 1),
+        ['x', a, b, ..] if let Some(c) = u32::from_str_radix(&format!("{a}{b}"), 16).ok().and_then(char::from_u32) => (c.to_string(), 3),
+        _ => ("\\".to_string(), 0),
+    }
+}
+
 pub fn ir_tokenize(s: &str) -> Vec<IRToken> {
     let mut chars: Vec<_> = s.chars().collect();
     chars.push('#'); // automatically closes off final idents.
@@ -50,6 +68,11 @@ pub fn ir_tokenize(s: &str) -> Vec<IRToken> {
                 ['*', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Mul)); i += 1; }
                 ['/', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Div)); i += 1; }
                 ['%', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Mod)); i += 1; }
+                ['<', '<', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Shl)); i += 2; }
+                ['>', '>', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Shr)); i += 2; }
+                ['&', ..] => { tokens.push(IRToken::BinOp(BinOpKind::BitAnd)); i += 1; }
+                ['|', ..] => { tokens.push(IRToken::BinOp(BinOpKind::BitOr)); i += 1; }
+                ['^', ..] => { tokens.push(IRToken::BinOp(BinOpKind::BitXor)); i += 1; }
                 ['<', '=', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Le)); i += 2; }
                 ['>', '=', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Ge)); i += 2; }
                 ['<', ..] => { tokens.push(IRToken::BinOp(BinOpKind::Lt)); i += 1; }
@@ -78,11 +101,17 @@ pub fn ir_tokenize(s: &str) -> Vec<IRToken> {
                 if c == delim {
                     tokens.push(IRToken::Str(s));
                     state = TokenizerState::None;
+                    i += 1;
+                } else if c == '\\' {
+                    let (x, len) = unescape(&chars[i+1..]);
+                    s.push_str(&x);
+                    state = TokenizerState::InStr(delim, s);
+                    i += 1 + len;
                 } else {
                     s.push(c);
                     state = TokenizerState::InStr(delim, s);
+                    i += 1;
                 }
-                i += 1;
             }
             TokenizerState::InIdent(mut s) => {
                 if ident_char(c) {
@@ -97,7 +126,16 @@ pub fn ir_tokenize(s: &str) -> Vec<IRToken> {
                         "jmp" => IRToken::Jmp,
                         "main" => IRToken::Main,
                         "print" => IRToken::Print,
+                        "eprint" => IRToken::Eprint,
+                        "write" => IRToken::Write,
                         "input" => IRToken::Input,
+                        "repr" => IRToken::Repr,
+                        "strlen" => IRToken::StrLen,
+                        "substr" => IRToken::Substr,
+                        "parse_int" => IRToken::ParseInt,
+                        "parse_float" => IRToken::ParseFloat,
+                        "trunc" => IRToken::Trunc,
+                        "truediv" => IRToken::TrueDiv,
                         "fail" => IRToken::Fail,
                         _ => IRToken::Symbol(Symbol::new(s)),
                     });
@@ -109,12 +147,28 @@ pub fn ir_tokenize(s: &str) -> Vec<IRToken> {
                     s.push(c);
                     state = TokenizerState::InInt(s);
                     i += 1;
+                } else if c == '.' || c == 'e' || c == 'E' {
+                    s.push(c);
+                    state = TokenizerState::InFloat(s);
+                    i += 1;
                 } else {
                     let int = s.parse().expect("Can't parse int!");
                     tokens.push(IRToken::Int(int));
                     state = TokenizerState::None;
                 }
             }
+            TokenizerState::InFloat(mut s) => {
+                // an exponent is `[eE][+-]?` followed by digits.
+                if int_char(c) || c == 'e' || c == 'E' || ((c == '-' || c == '+') && s.ends_with(['e', 'E'])) {
+                    s.push(c);
+                    state = TokenizerState::InFloat(s);
+                    i += 1;
+                } else {
+                    let float: f64 = s.parse().expect("Can't parse float!");
+                    tokens.push(IRToken::Float(finite(float).expect("Float out of range!")));
+                    state = TokenizerState::None;
+                }
+            }
             TokenizerState::InComment => {
                 if c == '\n' {
                     state = TokenizerState::None;
@@ -4,27 +4,39 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
     for stmt in stmts {
         ctxt.push(format!("# {stmt:?}"));
 
-        match stmt {
-            ASTStatement::Expr(e) => {
+        // the current line is kept in the frame for tracebacks.
+        if !matches!(stmt.kind, ASTStatementKind::Pass | ASTStatementKind::Scope(..)) {
+            ctxt.push(format!("@.frame.line = \"{}\"", stmt.span.line));
+        }
+
+        match &stmt.kind {
+            ASTStatementKind::Expr(e) => {
                 lower_expr(e, ctxt);
             },
-            ASTStatement::Assign(lhs, rhs) => {
-                let lhs = lower_pexpr(lhs, ctxt);
+            ASTStatementKind::Assign(targets, rhs) => {
+                // the rhs is evaluated first, then the targets are assigned from left to right.
                 let rhs = lower_expr(rhs, ctxt);
-                pexpr_store(&lhs, rhs, ctxt);
+                for t in targets {
+                    lower_assign(t, rhs.clone(), ctxt);
+                }
             },
-            ASTStatement::AugAssign(lhs, op, rhs) => {
+            ASTStatementKind::Del(targets) => {
+                for t in targets {
+                    let t = lower_pexpr(t, ctxt);
+                    pexpr_delete(&t, ctxt);
+                }
+            },
+            ASTStatementKind::AugAssign(lhs, op, rhs) => {
                 let lhs = lower_pexpr(lhs, ctxt);
+                // the subexpressions of the target are only evaluated once, by lower_pexpr.
                 let lhs_v = pexpr_load(&lhs, ctxt);
                 let rhs_v = lower_expr(rhs, ctxt);
 
-                // TODO try `__iadd__` etc. first.
-                let op = aug_op_attr_fallbacks(*op);
-                let out = lower_binop(op, lhs_v, rhs_v, ctxt);
+                let out = lower_aug_op(*op, lhs_v, rhs_v, ctxt);
 
                 pexpr_store(&lhs, out, ctxt);
             },
-            ASTStatement::If(cond, then, else_blk) => {
+            ASTStatementKind::If(cond, then, else_blk) => {
                 let cond = lower_expr(cond, ctxt);
                 let then_pid = ctxt.alloc_blk();
                 let else_pid = ctxt.alloc_blk();
@@ -47,13 +59,13 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
 
                 ctxt.focus_blk(post_pid);
             },
-            ASTStatement::While(cond, body, else_) => {
+            ASTStatementKind::While(cond, body, else_) => {
                 let pre_pid = ctxt.alloc_blk();
                 let body_pid = ctxt.alloc_blk();
                 let else_pid = ctxt.alloc_blk();
                 let post_pid = ctxt.alloc_blk();
 
-                ctxt.fl_mut().loop_stack.push((post_pid, pre_pid));
+                ctxt.fl_mut().blocks.push(Block::Loop { brk: post_pid, cont: pre_pid });
 
                 ctxt.push(format!("jmp {pre_pid}"));
 
@@ -69,6 +81,9 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
                     lower_body(body, ctxt);
                     ctxt.push(format!("jmp {pre_pid}"));
 
+                // `break` in the else branch refers to an outer loop.
+                ctxt.fl_mut().blocks.pop();
+
                 ctxt.focus_blk(else_pid);
                     if let Some(else_) = else_ {
                         lower_body(else_, ctxt);
@@ -76,41 +91,71 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
                     ctxt.push(format!("jmp {post_pid}"));
 
                 ctxt.focus_blk(post_pid);
-
-                ctxt.fl_mut().loop_stack.pop();
             },
-            ASTStatement::Break => {
-                let pid = ctxt.fl_mut().loop_stack.last().unwrap().0;
-                ctxt.push(format!("jmp {pid}"));
+            ASTStatementKind::Break | ASTStatementKind::Continue => {
+                // nameres already rejected them outside of loops.
+                let target = ctxt.fl().blocks.iter().rev().find_map(|b| match b {
+                    Block::Loop { brk, cont } => Some(if matches!(stmt.kind, ASTStatementKind::Break) { *brk } else { *cont }),
+                    _ => None,
+                }).unwrap();
+                lower_exit(Exit::Jump(target), ctxt);
                 return;
             },
-            ASTStatement::Continue => {
-                let pid = ctxt.fl_mut().loop_stack.last().unwrap().1;
-                ctxt.push(format!("jmp {pid}"));
-                return;
-            },
-            ASTStatement::Def(name, args, body) => {
-                // add a return, incase it's missing.
-                let mut body: Vec<ASTStatement> = body.iter().cloned().collect();
-                body.push(ASTStatement::Return(None));
-                let body = &*body;
+            ASTStatementKind::Def(name, params, body) => {
+                // defaults are evaluated at definition time.
+                let sig = lower_signature(params, ctxt);
 
                 let pid = Symbol::new_fresh(format!("f_{name}"));
                 ctxt.procs.insert(pid, Vec::new());
                 ctxt.stack.push(FnCtxt {
                     current_pid: pid,
                     lowering: Some(FnLowerCtxt {
-                        loop_stack: Vec::new(),
+                        blocks: Vec::new(),
+                        except_stack: Vec::new(),
                         ast_ptr: stmt as *const _,
+                        frame_ptr: stmt as *const _,
                     }),
                 });
 
-                for (i, a) in args.iter().enumerate() {
-                    ctxt.push(format!("@.frame.pylocals[\"{a}\"] = @.arg[{i}]"));
+                // a generator function only creates the generator, the body is run by the first `send`.
+                // The body runs in a frame of its own, see py_make_generator.
+                let generator = ctxt.generators.contains(&(stmt as *const _));
+                if generator {
+                    let body_pid = ctxt.alloc_blk();
+                    ctxt.push(String::from("%args = @.arg"));
+                    ctxt.push(String::from("@.arg = {}"));
+                    ctxt.push(String::from("@.arg.args = %args"));
+                    ctxt.push(format!("@.arg.resume = {body_pid}"));
+                    ctxt.push(String::from("jmp py_make_generator"));
+                    ctxt.focus_blk(body_pid);
+                }
+                let args = if generator { "@.frame.generator.args" } else { "@.arg" };
+
+                ctxt.push(format!("@.frame.fn_name = \"{name}\""));
+                ctxt.push(format!("@.frame.file = {}", fmt_str_literal(&stmt.span.file.to_string())));
+
+                // every call gets fresh cells.
+                let cells = vars_with_place(stmt, VarPlace::Cell, ctxt);
+                if !cells.is_empty() {
+                    ctxt.push(String::from("@.frame.cells = {}"));
+                    for c in cells {
+                        ctxt.push(format!("@.frame.cells[\"{c}\"] = {{}}"));
+                    }
+                }
+
+                // py_bind has already put the arguments into parameter order.
+                for (i, p) in params.iter().enumerate() {
+                    lower_var_assign(&p.name, format!("{args}[{i}]"), ctxt);
                 }
 
                 lower_body(body, ctxt);
 
+                // add a return, incase it's missing.
+                // The body is not cloned for this, as nameres identifies the inner defs by their address.
+                if !body.iter().any(|s| matches!(s.kind, ASTStatementKind::Return(_))) {
+                    lower_body(&[ASTStatementKind::Return(None).at(stmt.span)], ctxt);
+                }
+
                 ctxt.stack.pop();
 
                 let val = Symbol::new_fresh("functionbox");
@@ -118,21 +163,32 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
                 ctxt.push(format!("%{val}.type = @.singletons.function"));
                 ctxt.push(format!("%{val}.dict = {{}}"));
                 ctxt.push(format!("%{val}.payload = {pid}"));
+                ctxt.push(format!("%{val}.sig = {sig}"));
+
+                // capture the cells of the free variables.
+                ctxt.push(format!("%{val}.closure = {{}}"));
+                let frame_ptr = ctxt.fl().frame_ptr;
+                for v in vars_with_place(stmt, VarPlace::Closured, ctxt) {
+                    let src = match ctxt.nameres_tab[&(frame_ptr, v.clone())] {
+                        VarPlace::Cell => "cells",
+                        _ => "closure",
+                    };
+                    ctxt.push(format!("%{val}.closure[\"{v}\"] = @.frame.{src}[\"{v}\"]"));
+                }
 
                 lower_var_assign(name, format!("%{val}"), ctxt);
             },
-            ASTStatement::Return(obj) => {
+            ASTStatementKind::Return(obj) => {
                 let mut n = format!("@.singletons.none");
                 if let Some(o) = obj {
                     n = lower_expr(o, ctxt);
                 }
-                ctxt.push(format!("@.ret = {n}"));
-                ctxt.push(format!("jmp pop_stack"));
+                lower_exit(Exit::Return(n), ctxt);
                 return;
             },
-            ASTStatement::Pass => {},
-            ASTStatement::Scope(..) => {}, // Scope is handled in nameres.
-            ASTStatement::Class(name, args, body) => {
+            ASTStatementKind::Pass => {},
+            ASTStatementKind::Scope(..) => {}, // Scope is handled in nameres.
+            ASTStatementKind::Class(name, args, body) => {
                 let args: Vec<String> = args.iter().map(|x| lower_expr(x, ctxt)).collect();
 
                 let old_ptr = ctxt.fl().ast_ptr;
@@ -149,6 +205,7 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
                 let cl = ctxt.alloc_irlocal("class_obj");
                 ctxt.push(format!("{cl} = {{}}"));
                 ctxt.push(format!("{cl}.type = @.singletons.type"));
+                ctxt.push(format!("{cl}.name = \"{name}\""));
                 ctxt.push(format!("{cl}.dict = {dict}"));
 
                 let suc = ctxt.alloc_blk();
@@ -164,90 +221,141 @@ pub fn lower_body(stmts: &[ASTStatement], ctxt: &mut Ctxt) {
                 ctxt.focus_blk(suc);
                 ctxt.push(format!("@.frame.pylocals = {old_namespace}"));
 
-                lower_var_assign(name, format!("{cl}"), ctxt);
                 ctxt.fl_mut().ast_ptr = old_ptr;
+                lower_var_assign(name, format!("{cl}"), ctxt);
             },
-            ASTStatement::Try(body, excepts) => {
-                let suc = ctxt.alloc_blk();
+            ASTStatementKind::Try(body, excepts, else_, finally) => {
+                lower_try(body, excepts, else_.as_deref(), finally.as_deref(), ctxt);
+            },
+            ASTStatementKind::Raise(Some(e)) => {
+                let e = lower_expr(e, ctxt);
+                ctxt.push(String::from("@.arg = {}"));
+                ctxt.push(format!("@.arg.obj = {e}"));
+                ctxt.push(String::from("jmp py_raise"));
+                return;
+            }
+            ASTStatementKind::Raise(None) => {
+                // re-raises the exception of the innermost except clause.
+                if let Some(exc) = ctxt.fl().except_stack.last() {
+                    let exc = exc.clone();
+                    ctxt.push(format!("@.exc = {exc}"));
+                    ctxt.push(String::from("jmp raise"));
+                } else {
+                    ctxt.push(String::from("jmp py_reraise"));
+                }
+                return;
+            }
+            ASTStatementKind::For(target, expr, body, else_) => {
+                let pre_pid = ctxt.alloc_blk();
+                let stop_pid = ctxt.alloc_blk();
+                let else_pid = ctxt.alloc_blk();
+                let post_pid = ctxt.alloc_blk();
 
-                let mut pids = Vec::new();
+                let iterable = lower_expr(expr, ctxt);
+                let iter_fn = pexpr_load(&PlaceExpr::Attr(iterable, String::from("__iter__")), ctxt);
+                let it = lower_fn_call(iter_fn, Vec::new(), ctxt);
+                ctxt.push(format!("jmp {pre_pid}"));
 
-                // We push to the handler stack in reverted order, as the first `except` should be at the top of the stack.
-                for _except in excepts.iter().rev() {
-                    let h = ctxt.alloc_irlocal("handler");
-                    let except_pid = ctxt.alloc_blk();
-                    pids.push(except_pid);
+                ctxt.focus_blk(pre_pid);
+                    ctxt.push(format!("@.frame.line = \"{}\"", stmt.span.line));
 
-                    // push handler stack
+                    // only the call to `__next__` is guarded against StopIteration, not the loop body.
+                    let h = ctxt.alloc_irlocal("handler");
                     ctxt.push(format!("{h} = {{}}"));
                     ctxt.push(format!("{h}.parent = @.handler"));
                     ctxt.push(format!("{h}.frame = @.frame"));
-                    ctxt.push(format!("{h}.pid = {except_pid}"));
+                    ctxt.push(format!("{h}.pid = {stop_pid}"));
                     ctxt.push(format!("@.handler = {h}"));
-                }
 
-                lower_body(body, ctxt);
+                    let next_fn = pexpr_load(&PlaceExpr::Attr(it, String::from("__next__")), ctxt);
+                    let val = lower_fn_call(next_fn, Vec::new(), ctxt);
+                    ctxt.push(format!("@.handler = {h}.parent"));
 
-                // pop handler stack
-                for _except in excepts.iter() {
-                    ctxt.push(format!("@.handler = @.handler.parent"));
-                }
+                    lower_assign(target, val, ctxt);
 
-                ctxt.push(format!("jmp {suc}"));
+                    ctxt.fl_mut().blocks.push(Block::Loop { brk: post_pid, cont: pre_pid });
+                    lower_body(body, ctxt);
+                    ctxt.push(format!("jmp {pre_pid}"));
+                    ctxt.fl_mut().blocks.pop();
 
-                for (pid, except) in pids.iter().zip(excepts.iter().rev()) {
-                    ctxt.focus_blk(*pid);
-                        lower_body(&except.body, ctxt);
-                        ctxt.push(format!("jmp {suc}"));
-                }
+                // other exceptions are passed on to the enclosing handler.
+                ctxt.focus_blk(stop_pid);
+                    ctxt.push(String::from("@.arg = {}"));
+                    ctxt.push(String::from("@.arg.obj = @.exc"));
+                    ctxt.push(String::from("@.arg.cls = @.singletons.stop_iteration"));
+                    ctxt.push(format!("@.arg.suc_true = {else_pid}"));
+                    ctxt.push(String::from("@.arg.suc_false = raise"));
+                    ctxt.push(String::from("jmp py_exc_match"));
 
-                ctxt.focus_blk(suc);
-            },
-            ASTStatement::Raise(_body) => {
-                ctxt.push(format!("jmp raise"))
-            }
-            ASTStatement::For(v, expr, body) => {
-                // The $ sign prevents collisions with user-defined variables.
-                let hv = Symbol::new_fresh("$hiddenvar");
-
-                let expr = ASTExpr::FnCall(Box::new(
-                    ASTExpr::Attribute(Box::new(expr.clone()), String::from("__iter__"))),
-                    vec![]);
-                let mut body = body.clone();
-                body.insert(0, ASTStatement::Assign(
-                    ASTExpr::Var(v.to_string()),
-                    ASTExpr::FnCall(
-                        Box::new(ASTExpr::Attribute(
-                            Box::new(ASTExpr::Var(hv.to_string())),
-                            "__next__".to_string(),
-                        )),
-                   vec![]),
-                ));
-                let bod = vec![
-                    ASTStatement::Assign(ASTExpr::Var(hv.to_string()), expr),
-                    ASTStatement::While(ASTExpr::Bool(true), body, None),
-                ];
-                let except = Except {
-                    ty: None,
-                    body: vec![ASTStatement::Pass],
-                };
-                let stmt = ASTStatement::Try(bod, vec![except]);
-                lower_body(&[stmt], ctxt);
+                ctxt.focus_blk(else_pid);
+                    if let Some(else_) = else_ {
+                        lower_body(else_, ctxt);
+                    }
+                    ctxt.push(format!("jmp {post_pid}"));
+
+                ctxt.focus_blk(post_pid);
             },
         }
     }
 }
 
+// builds the Signature table, see types.ir.
+fn lower_signature(params: &[Param], ctxt: &mut Ctxt) -> Lowered {
+    let mut defaults = Vec::new();
+    for (i, p) in params.iter().enumerate() {
+        if let Some(d) = &p.default {
+            defaults.push((i, lower_expr(d, ctxt)));
+        }
+    }
+
+    let sig = ctxt.alloc_irlocal("sig");
+    ctxt.push(format!("{sig} = {{}}"));
+    ctxt.push(format!("{sig}.n = {}", params.len()));
+    let npos = params.iter().take_while(|p| p.kind == ParamKind::Normal).count();
+    ctxt.push(format!("{sig}.npos = {npos}"));
+    ctxt.push(format!("{sig}.index = {{}}"));
+    ctxt.push(format!("{sig}.defaults = {{}}"));
+    for (i, p) in params.iter().enumerate() {
+        let name = &p.name;
+        match p.kind {
+            ParamKind::Normal => ctxt.push(format!("{sig}.index[\"{name}\"] = {i}")),
+            ParamKind::VarArgs => ctxt.push(format!("{sig}.varargs = {i}")),
+            ParamKind::VarKwArgs => ctxt.push(format!("{sig}.varkw = {i}")),
+        }
+    }
+    for (i, d) in &defaults {
+        ctxt.push(format!("{sig}.defaults[{i}] = {d}"));
+    }
+
+    // plain signatures can skip py_bind, see py_call_dispatch.
+    let plain = defaults.is_empty() && npos == params.len();
+    let plain = if plain { "True" } else { "False" };
+    ctxt.push(format!("{sig}.plain = {plain}"));
+
+    sig
+}
+
 pub fn lower_var_assign(var: &str, val: String, ctxt: &mut Ctxt) {
-    let ns = find_namespace(var, ctxt);
-    ctxt.push(format!("{ns}[\"{var}\"] = {val}"));
+    let v = find_var(var, ctxt);
+    ctxt.push(format!("{v} = {val}"));
 }
 
-pub fn find_namespace(v: &str, ctxt: &mut Ctxt) -> Lowered {
+// the location of the python variable `v`, see VarPlace.
+pub fn find_var(v: &str, ctxt: &mut Ctxt) -> Lowered {
     let k = (ctxt.fl().ast_ptr, v.to_string());
     match ctxt.nameres_tab.get(&k) {
-        Some(VarPlace::Local) => format!("@.frame.pylocals"),
-        _ => format!("@.globals"),
+        Some(VarPlace::Local) => format!("@.frame.pylocals[\"{v}\"]"),
+        Some(VarPlace::Cell) => format!("@.frame.cells[\"{v}\"].value"),
+        Some(VarPlace::Closured) => format!("@.frame.closure[\"{v}\"].value"),
+        Some(VarPlace::Global) | None => format!("@.globals[\"{v}\"]"),
     }
 }
 
+// the variables of the def `ptr`, that have the place `place`.
+fn vars_with_place(ptr: *const ASTStatement, place: VarPlace, ctxt: &Ctxt) -> Vec<String> {
+    ctxt.nameres_tab.iter()
+        .filter(|((p, _), pl)| *p == ptr && **pl == place)
+        .map(|((_, v), _)| v.clone())
+        .collect()
+}
+
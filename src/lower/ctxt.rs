@@ -3,6 +3,7 @@ use crate::lower::*;
 pub(in crate::lower) struct Ctxt {
     pub stack: Vec<FnCtxt>,
     pub nameres_tab: NameResTable,
+    pub generators: Set<*const ASTStatement>,
     pub procs: Map<Symbol, Vec<String>>,
 }
 
@@ -12,11 +13,41 @@ pub(in crate::lower) struct FnCtxt {
 }
 
 pub(in crate::lower) struct FnLowerCtxt {
-    pub loop_stack: Vec<(/*break*/ Symbol, /*continue*/ Symbol)>,
+    pub blocks: Vec<Block>,
+
+    // the irlocals holding the exceptions of the enclosing except clauses, used by bare `raise`.
+    pub except_stack: Vec<Lowered>,
 
     // the original def stmt we are lowering.
     // set to 0 for the main function.
     pub ast_ptr: *const ASTStatement,
+
+    // the def stmt, whose frame we are running in.
+    // differs from ast_ptr within class bodies, as they run in the frame of the enclosing function.
+    pub frame_ptr: *const ASTStatement,
+}
+
+// a construct enclosing the current statement, that `break`, `continue` and `return` might have to leave.
+pub(in crate::lower) enum Block {
+    Loop { brk: Symbol, cont: Symbol },
+    Finally(FinallyBlock),
+
+    // the irlocal of an exception handler, that is installed while lowering the body of a try statement.
+    Handler(Lowered),
+}
+
+pub(in crate::lower) struct FinallyBlock {
+    // an irlocal {action: ProcId, value: Object, exc: Object}.
+    // after the finally body, we continue with `action`.
+    pub pending: Lowered,
+    pub body_pid: Symbol,
+
+    // the irlocal of the finally handler, its parent is the handler stack from before the try statement.
+    pub handler: Lowered,
+
+    // these continue a `return` (or a jump to a loop target) after the finally body, see lower_exit.
+    pub return_pid: Option<Symbol>,
+    pub jump_pids: Map</*target*/ Symbol, Symbol>,
 }
 
 impl Ctxt {
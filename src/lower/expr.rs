@@ -1,30 +1,39 @@
 use crate::lower::*;
 
 pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
-    let out = match e {
-        ASTExpr::FnCall(f, args) => {
+    let out = match &e.kind {
+        ASTExprKind::FnCall(f, args) => {
             let f = lower_expr(f, ctxt);
 
-            let mut args2 = Vec::new();
-            for a in args.iter() {
-                args2.push(lower_expr(a, ctxt));
+            let positional: Option<Vec<&ASTExpr>> = args.iter().map(|a| match a {
+                ASTArg::Positional(e) => Some(e),
+                _ => None,
+            }).collect();
+
+            match positional {
+                Some(positional) => {
+                    let mut args2 = Vec::new();
+                    for a in positional {
+                        args2.push(lower_expr(a, ctxt));
+                    }
+                    lower_fn_call(f, args2, ctxt)
+                },
+                None => lower_general_fn_call(f, args, ctxt),
             }
-
-            lower_fn_call(f, args2, ctxt)
         },
-        ASTExpr::Var(..) | ASTExpr::Attribute(..) | ASTExpr::BinOp(ASTBinOpKind::Subscript, ..) => {
+        ASTExprKind::Var(..) | ASTExprKind::Attribute(..) | ASTExprKind::BinOp(ASTBinOpKind::Subscript, ..) => {
             let e = lower_pexpr(e, ctxt);
             pexpr_load(&e, ctxt)
         },
-        ASTExpr::Str(s) => {
+        ASTExprKind::Str(s) => {
             let t = Symbol::new_fresh("strbox".to_string());
             ctxt.push(format!("%{t} = {{}}"));
             ctxt.push(format!("%{t}.type = @.singletons.str"));
-            ctxt.push(format!("%{t}.payload = \"{s}\""));
+            ctxt.push(format!("%{t}.payload = {}", fmt_str_literal(s)));
 
             format!("%{t}")
         },
-        ASTExpr::Int(i) => {
+        ASTExprKind::Int(i) => {
             let t = Symbol::new_fresh("intbox".to_string());
             ctxt.push(format!("%{t} = {{}}"));
             ctxt.push(format!("%{t}.type = @.singletons.int"));
@@ -32,13 +41,21 @@ pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
 
             format!("%{t}")
         },
-        ASTExpr::Bool(b) => {
+        ASTExprKind::Float(x) => {
+            let t = Symbol::new_fresh("floatbox".to_string());
+            ctxt.push(format!("%{t} = {{}}"));
+            ctxt.push(format!("%{t}.type = @.singletons.float"));
+            ctxt.push(format!("%{t}.payload = {}", fmt_float_literal(*x)));
+
+            format!("%{t}")
+        },
+        ASTExprKind::Bool(b) => {
             match *b {
                 true => format!("@.singletons.true"),
                 false => format!("@.singletons.false"),
             }
         },
-        ASTExpr::BinOp(op@(ASTBinOpKind::And | ASTBinOpKind::Or), l, r) => {
+        ASTExprKind::BinOp(op@(ASTBinOpKind::And | ASTBinOpKind::Or), l, r) => {
             let l = lower_expr(l, ctxt);
 
             let suc = ctxt.alloc_blk();
@@ -48,18 +65,16 @@ pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
             ctxt.push(format!("{arg} = {{}}"));
             ctxt.push(format!("{arg}.elem = {l}"));
 
-            let jmptab = Symbol::new_fresh("jmptab");
-            ctxt.push(format!("%{jmptab} = {{}}"));
-
-            if let ASTBinOpKind::And = op {
-                ctxt.push(format!("%{jmptab}[{l}.payload] = {suc}"));
-                ctxt.push(format!("%{jmptab}[True] = {snd}"));
-            } else {
-                ctxt.push(format!("%{jmptab}[{l}.payload] = {snd}"));
-                ctxt.push(format!("%{jmptab}[True] = {suc}"));
-            }
-
-            ctxt.push(format!("jmp %{jmptab}[{l}.payload]"));
+            // `a and b` only evaluates `b` if `a` is truthy, `a or b` only if it's falsy.
+            let (suc_true, suc_false) = match op {
+                ASTBinOpKind::And => (snd, suc),
+                _ => (suc, snd),
+            };
+            ctxt.push(String::from("@.arg = {}"));
+            ctxt.push(format!("@.arg.suc_true = {suc_true}"));
+            ctxt.push(format!("@.arg.suc_false = {suc_false}"));
+            ctxt.push(format!("@.arg.obj = {l}"));
+            ctxt.push(String::from("jmp branch_truthy"));
 
             ctxt.focus_blk(snd);
                 let r = lower_expr(r, ctxt);
@@ -69,12 +84,69 @@ pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
             ctxt.focus_blk(suc);
                 format!("{arg}.elem")
         },
-        ASTExpr::BinOp(kind, l, r) => {
+        ASTExprKind::BinOp(kind, l, r) => {
             let l = lower_expr(l, ctxt);
             let r = lower_expr(r, ctxt);
-            lower_binop(*kind, l, r, ctxt)
+            lower_compare_or_binop(*kind, l, r, ctxt)
         },
-        ASTExpr::UnOp(ASTUnOpKind::Neg, e) => {
+        // `a < b < c` is `a < b and b < c`, but `b` is only evaluated once.
+        ASTExprKind::Compare(first, chain) => {
+            let out = ctxt.alloc_irlocal("compare_out");
+            let post = ctxt.alloc_blk();
+
+            let mut l = lower_expr(first, ctxt);
+            for (i, (op, r)) in chain.iter().enumerate() {
+                let r = lower_expr(r, ctxt);
+                let res = lower_compare_or_binop(*op, l, r.clone(), ctxt);
+                ctxt.push(format!("{out} = {res}"));
+                if i == chain.len() - 1 { break; }
+
+                // the first false comparison is the result.
+                let next = ctxt.alloc_blk();
+                ctxt.push(String::from("@.arg = {}"));
+                ctxt.push(format!("@.arg.suc_true = {next}"));
+                ctxt.push(format!("@.arg.suc_false = {post}"));
+                ctxt.push(format!("@.arg.obj = {out}"));
+                ctxt.push(String::from("jmp branch_truthy"));
+
+                ctxt.focus_blk(next);
+                l = r;
+            }
+            ctxt.push(format!("jmp {post}"));
+
+            ctxt.focus_blk(post);
+                out
+        },
+        ASTExprKind::IfExpr(cond, then, else_) => {
+            let cond = lower_expr(cond, ctxt);
+            let out = ctxt.alloc_irlocal("ifexpr_out");
+            let then_pid = ctxt.alloc_blk();
+            let else_pid = ctxt.alloc_blk();
+            let post_pid = ctxt.alloc_blk();
+            ctxt.push(String::from("@.arg = {}"));
+            ctxt.push(format!("@.arg.suc_true = {then_pid}"));
+            ctxt.push(format!("@.arg.suc_false = {else_pid}"));
+            ctxt.push(format!("@.arg.obj = {cond}"));
+            ctxt.push(String::from("jmp branch_truthy"));
+
+            ctxt.focus_blk(then_pid);
+                let then = lower_expr(then, ctxt);
+                ctxt.push(format!("{out} = {then}"));
+                ctxt.push(format!("jmp {post_pid}"));
+
+            ctxt.focus_blk(else_pid);
+                let else_ = lower_expr(else_, ctxt);
+                ctxt.push(format!("{out} = {else_}"));
+                ctxt.push(format!("jmp {post_pid}"));
+
+            ctxt.focus_blk(post_pid);
+                out
+        },
+        ASTExprKind::UnOp(ASTUnOpKind::Not, e) => {
+            let e = lower_expr(e, ctxt);
+            lower_not(e, ctxt)
+        },
+        ASTExprKind::UnOp(ASTUnOpKind::Neg, e) => {
             let e = lower_expr(e, ctxt);
 
             let suc = ctxt.alloc_blk();
@@ -94,25 +166,31 @@ pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
             ctxt.focus_blk(suc);
                 format!("@.ret")
         },
-        ASTExpr::None => format!("@.singletons.none"),
-        ASTExpr::List(elems) => {
-            let len = elems.len();
-            let t = Symbol::new_fresh("listbox");
-            ctxt.push(format!("%{t} = {{}}"));
-            ctxt.push(format!("%{t}.type = @.singletons.list"));
-            ctxt.push(format!("%{t}.dict = {{}}"));
-            ctxt.push(format!("%{t}.payload = {{}}"));
-            for (i, a) in elems.iter().enumerate() {
-                let a = lower_expr(a, ctxt);
-                ctxt.push(format!("%{t}.payload[{i}] = {a}"));
+        ASTExprKind::None => format!("@.singletons.none"),
+        ASTExprKind::Yield(v) => {
+            let v = match v {
+                Some(v) => lower_expr(v, ctxt),
+                None => String::from("@.singletons.none"),
+            };
+            lower_yield(v, ctxt)
+        },
+        ASTExprKind::YieldFrom(e) => {
+            let e = lower_expr(e, ctxt);
+            lower_yield_from(e, ctxt)
+        },
+        ASTExprKind::List(elems) => lower_sequence("list", elems, ctxt),
+        ASTExprKind::Tuple(elems) => lower_sequence("tuple", elems, ctxt),
+        ASTExprKind::Dict(pairs) => {
+            let mut pairs2 = Vec::new();
+            for (k, v) in pairs {
+                let k = lower_expr(k, ctxt);
+                let v = lower_expr(v, ctxt);
+                pairs2.push((k, v));
             }
-            ctxt.push(format!("%{t}.length = {{}}"));
-            ctxt.push(format!("%{t}.length.type = @.singletons.int"));
-            ctxt.push(format!("%{t}.length.payload = {len}"));
 
-            format!("%{t}")
+            lower_dict(pairs2, ctxt)
         },
-        ASTExpr::Slice(b) => {
+        ASTExprKind::Slice(b) => {
             let (a, b, c) = &**b;
             let a = a.as_ref().map(|a| lower_expr(a, ctxt));
             let b = b.as_ref().map(|b| lower_expr(b, ctxt));
@@ -139,6 +217,58 @@ pub fn lower_expr(e: &ASTExpr, ctxt: &mut Ctxt) -> Lowered {
     format!("{irl}")
 }
 
+// builds a list or tuple object. Both share the same layout.
+fn lower_sequence(ty: &str, elems: &[ASTExpr], ctxt: &mut Ctxt) -> Lowered {
+    // the elements might contain calls, which would end the current proc.
+    // Hence they are lowered before the box is created.
+    let elems: Vec<Lowered> = elems.iter().map(|a| lower_expr(a, ctxt)).collect();
+
+    let len = elems.len();
+    let t = Symbol::new_fresh(format!("{ty}box"));
+    ctxt.push(format!("%{t} = {{}}"));
+    ctxt.push(format!("%{t}.type = @.singletons.{ty}"));
+    ctxt.push(format!("%{t}.dict = {{}}"));
+    ctxt.push(format!("%{t}.payload = {{}}"));
+    for (i, a) in elems.iter().enumerate() {
+        ctxt.push(format!("%{t}.payload[{i}] = {a}"));
+    }
+    ctxt.push(format!("%{t}.length = {{}}"));
+    ctxt.push(format!("%{t}.length.type = @.singletons.int"));
+    ctxt.push(format!("%{t}.length.payload = {len}"));
+
+    format!("%{t}")
+}
+
+// the dict is built in place, so that every literal has its own allocation site.
+fn lower_dict(pairs: Vec<(Lowered, Lowered)>, ctxt: &mut Ctxt) -> Lowered {
+    let d = ctxt.alloc_irlocal("dictbox");
+    ctxt.push(format!("{d} = {{}}"));
+    ctxt.push(format!("{d}.type = @.singletons.dict"));
+    ctxt.push(format!("{d}.dict = {{}}"));
+    ctxt.push(format!("{d}.buckets = {{}}"));
+    ctxt.push(format!("{d}.head = {{}}"));
+    ctxt.push(format!("{d}.last = {d}.head"));
+    ctxt.push(format!("{d}.length = {{}}"));
+    ctxt.push(format!("{d}.length.type = @.singletons.int"));
+    ctxt.push(format!("{d}.length.payload = 0"));
+
+    for (k, v) in pairs {
+        let suc = ctxt.alloc_blk();
+        ctxt.push(String::from("@.arg = {}"));
+        ctxt.push(String::from("@.arg.f = fn_dict_setitem"));
+        ctxt.push(String::from("@.arg.farg = {}"));
+        ctxt.push(format!("@.arg.farg[0] = {d}"));
+        ctxt.push(format!("@.arg.farg[1] = {k}"));
+        ctxt.push(format!("@.arg.farg[2] = {v}"));
+        ctxt.push(format!("@.arg.suc = {suc}"));
+        ctxt.push(String::from("jmp call_fn"));
+
+        ctxt.focus_blk(suc);
+    }
+
+    d
+}
+
 pub fn lower_fn_call(f: Lowered, args: Vec<Lowered>, ctxt: &mut Ctxt) -> Lowered {
     let suc = ctxt.alloc_blk();
     let arg = ctxt.alloc_irlocal("arg");
@@ -160,6 +290,152 @@ pub fn lower_fn_call(f: Lowered, args: Vec<Lowered>, ctxt: &mut Ctxt) -> Lowered
 }
 
 
+// a call with keyword arguments or argument unpacking.
+fn lower_general_fn_call(f: Lowered, args: &[ASTArg], ctxt: &mut Ctxt) -> Lowered {
+    let mut args2 = Vec::new();
+    for a in args {
+        let (ASTArg::Positional(e) | ASTArg::Keyword(_, e) | ASTArg::Star(e) | ASTArg::DoubleStar(e)) = a;
+        args2.push(lower_expr(e, ctxt));
+    }
+
+    let arg = ctxt.alloc_irlocal("arg");
+    ctxt.push(format!("{arg} = {{}}"));
+    ctxt.push(format!("{arg}.f = {f}"));
+    ctxt.push(format!("{arg}.args = {{}}"));
+    ctxt.push(format!("{arg}.nargs = 0"));
+    ctxt.push(format!("{arg}.kwargs = {{}}"));
+    ctxt.push(format!("{arg}.nkwargs = 0"));
+
+    for (a, v) in args.iter().zip(args2) {
+        match a {
+            ASTArg::Positional(_) => {
+                ctxt.push(format!("{arg}.args[{arg}.nargs] = {v}"));
+                ctxt.push(format!("{arg}.nargs = {arg}.nargs + 1"));
+            },
+            ASTArg::Keyword(name, _) => {
+                let kw = Symbol::new_fresh("kw");
+                ctxt.push(format!("%{kw} = {{}}"));
+                ctxt.push(format!("%{kw}.name = \"{name}\""));
+                ctxt.push(format!("%{kw}.value = {v}"));
+                ctxt.push(format!("{arg}.kwargs[{arg}.nkwargs] = %{kw}"));
+                ctxt.push(format!("{arg}.nkwargs = {arg}.nkwargs + 1"));
+            },
+            ASTArg::Star(_) | ASTArg::DoubleStar(_) => {
+                let helper = if let ASTArg::Star(_) = a { "py_star_args" } else { "py_star_kwargs" };
+                let suc = ctxt.alloc_blk();
+                ctxt.push(String::from("@.arg = {}"));
+                ctxt.push(format!("@.arg.call = {arg}"));
+                ctxt.push(format!("@.arg.obj = {v}"));
+                ctxt.push(format!("@.arg.suc = {suc}"));
+                ctxt.push(format!("jmp {helper}"));
+
+                ctxt.focus_blk(suc);
+            },
+        }
+    }
+
+    let suc = ctxt.alloc_blk();
+    ctxt.push(format!("{arg}.suc = {suc}"));
+    ctxt.push(format!("@.arg = {arg}"));
+    ctxt.push(String::from("jmp py_call"));
+
+    ctxt.focus_blk(suc);
+
+    let out = ctxt.alloc_irlocal("lower_fn_out");
+    ctxt.push(format!("{out} = @.ret"));
+    out
+}
+
+// the comparisons `is`, `in` and their negations are not dispatched like the other binary operators.
+fn lower_compare_or_binop(kind: ASTBinOpKind, l: Lowered, r: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    match kind {
+        ASTBinOpKind::Is | ASTBinOpKind::IsNot => {
+            // objects are identical, iff they are the same table.
+            let (same, different) = match kind {
+                ASTBinOpKind::Is => ("true", "false"),
+                _ => ("false", "true"),
+            };
+            let t = Symbol::new_fresh("istab");
+            ctxt.push(format!("%{t} = {{}}"));
+            ctxt.push(format!("%{t}[{l}] = @.singletons.{different}"));
+            ctxt.push(format!("%{t}[{r}] = @.singletons.{same}"));
+            format!("%{t}[{l}]")
+        },
+        ASTBinOpKind::In => lower_contains(l, r, ctxt),
+        ASTBinOpKind::NotIn => {
+            let res = lower_contains(l, r, ctxt);
+            lower_not(res, ctxt)
+        },
+        _ => lower_binop(kind, l, r, ctxt),
+    }
+}
+
+// `elem in obj`
+fn lower_contains(elem: Lowered, obj: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    let suc = ctxt.alloc_blk();
+    ctxt.push(String::from("@.arg = {}"));
+    ctxt.push(format!("@.arg.elem = {elem}"));
+    ctxt.push(format!("@.arg.obj = {obj}"));
+    ctxt.push(format!("@.arg.suc = {suc}"));
+    ctxt.push(String::from("jmp py_contains"));
+
+    ctxt.focus_blk(suc);
+        let out = ctxt.alloc_irlocal("contains_out");
+        ctxt.push(format!("{out} = @.ret"));
+        out
+}
+
+// `not v`, a bool.
+fn lower_not(v: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    let out = ctxt.alloc_irlocal("not_out");
+    let true_pid = ctxt.alloc_blk();
+    let false_pid = ctxt.alloc_blk();
+    let post_pid = ctxt.alloc_blk();
+    ctxt.push(String::from("@.arg = {}"));
+    ctxt.push(format!("@.arg.suc_true = {true_pid}"));
+    ctxt.push(format!("@.arg.suc_false = {false_pid}"));
+    ctxt.push(format!("@.arg.obj = {v}"));
+    ctxt.push(String::from("jmp branch_truthy"));
+
+    ctxt.focus_blk(true_pid);
+        ctxt.push(format!("{out} = @.singletons.false"));
+        ctxt.push(format!("jmp {post_pid}"));
+
+    ctxt.focus_blk(false_pid);
+        ctxt.push(format!("{out} = @.singletons.true"));
+        ctxt.push(format!("jmp {post_pid}"));
+
+    ctxt.focus_blk(post_pid);
+        out
+}
+
+// `l op= r` tries the in-place operator first, see py_inplace_binop.
+pub fn lower_aug_op(op: ASTAugOpKind, l: Lowered, r: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    let (i_op, l_op) = aug_op_attrs(op);
+    let suc = ctxt.alloc_blk();
+    let arg = Symbol::new_fresh("arg");
+    ctxt.push(format!("%{arg} = {{}}"));
+    ctxt.push(format!("%{arg}.suc = {suc}"));
+
+    ctxt.push(format!("%{arg}.lhs = {l}"));
+    ctxt.push(format!("%{arg}.rhs = {r}"));
+
+    ctxt.push(format!("%{arg}.i_op = {{}}"));
+    ctxt.push(format!("%{arg}.i_op.obj = @.singletons.str"));
+    ctxt.push(format!("%{arg}.i_op.payload = \"{i_op}\""));
+    ctxt.push(format!("%{arg}.l_op = {{}}"));
+    ctxt.push(format!("%{arg}.l_op.obj = @.singletons.str"));
+    ctxt.push(format!("%{arg}.l_op.payload = \"{l_op}\""));
+    ctxt.push(format!("@.arg = %{arg}"));
+
+    ctxt.push(String::from("jmp py_inplace_binop"));
+
+    ctxt.focus_blk(suc);
+        let out = ctxt.alloc_irlocal("lower_aug_op");
+        ctxt.push(format!("{out} = @.ret"));
+        out
+}
+
 pub fn lower_binop(kind: ASTBinOpKind, l: Lowered, r: Lowered, ctxt: &mut Ctxt) -> Lowered {
     let l_op = op_attrs(kind);
     let suc = ctxt.alloc_blk();
@@ -189,6 +465,7 @@ pub fn op_attrs(op: ASTBinOpKind) -> &'static str {
         ASTBinOpKind::Minus => "__sub__",
         ASTBinOpKind::Mul => "__mul__",
         ASTBinOpKind::Div => "__truediv__",
+        ASTBinOpKind::FloorDiv => "__floordiv__",
         ASTBinOpKind::Mod => "__mod__",
         ASTBinOpKind::Lt => "__lt__",
         ASTBinOpKind::Gt => "__gt__",
@@ -197,26 +474,33 @@ pub fn op_attrs(op: ASTBinOpKind) -> &'static str {
         ASTBinOpKind::IsEqual => "__eq__",
         ASTBinOpKind::IsNotEqual => "__ne__",
         ASTBinOpKind::Pow => "__pow__",
+        ASTBinOpKind::LShift => "__lshift__",
+        ASTBinOpKind::RShift => "__rshift__",
+        ASTBinOpKind::BitAnd => "__and__",
+        ASTBinOpKind::BitOr => "__or__",
+        ASTBinOpKind::BitXor => "__xor__",
         ASTBinOpKind::Subscript => "__getitem__",
 
         ASTBinOpKind::And | ASTBinOpKind::Or => unreachable!(),
+        ASTBinOpKind::In | ASTBinOpKind::NotIn | ASTBinOpKind::Is | ASTBinOpKind::IsNot => unreachable!(),
     }
 }
 
-pub fn aug_op_attrs(op: ASTAugOpKind) -> &'static str {
-    match op {
-        ASTAugOpKind::PlusEq => "__iadd__",
-        ASTAugOpKind::MinusEq => "__isub__",
-        ASTAugOpKind::MulEq => "__imul__",
-        ASTAugOpKind::DivEq => "__itruediv__",
-    }
-}
-
-pub fn aug_op_attr_fallbacks(op: ASTAugOpKind) -> ASTBinOpKind {
+// the in-place operator, and the binary operator it falls back to.
+pub fn aug_op_attrs(op: ASTAugOpKind) -> (&'static str, &'static str) {
     match op {
-        ASTAugOpKind::PlusEq => ASTBinOpKind::Plus,
-        ASTAugOpKind::MinusEq => ASTBinOpKind::Minus,
-        ASTAugOpKind::MulEq => ASTBinOpKind::Mul,
-        ASTAugOpKind::DivEq => ASTBinOpKind::Div,
+        ASTAugOpKind::PlusEq => ("__iadd__", "__add__"),
+        ASTAugOpKind::MinusEq => ("__isub__", "__sub__"),
+        ASTAugOpKind::MulEq => ("__imul__", "__mul__"),
+        ASTAugOpKind::MatMulEq => ("__imatmul__", "__matmul__"),
+        ASTAugOpKind::DivEq => ("__itruediv__", "__truediv__"),
+        ASTAugOpKind::FloorDivEq => ("__ifloordiv__", "__floordiv__"),
+        ASTAugOpKind::ModEq => ("__imod__", "__mod__"),
+        ASTAugOpKind::PowEq => ("__ipow__", "__pow__"),
+        ASTAugOpKind::RShiftEq => ("__irshift__", "__rshift__"),
+        ASTAugOpKind::LShiftEq => ("__ilshift__", "__lshift__"),
+        ASTAugOpKind::AndEq => ("__iand__", "__and__"),
+        ASTAugOpKind::XorEq => ("__ixor__", "__xor__"),
+        ASTAugOpKind::OrEq => ("__ior__", "__or__"),
     }
 }
@@ -0,0 +1,91 @@
+use crate::lower::*;
+
+// suspends the generator with the value `v`, see py_yield.
+// Evaluates to the value passed to the `send` that resumes us.
+pub fn lower_yield(v: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    let resume = ctxt.alloc_blk();
+    ctxt.push(String::from("@.arg = {}"));
+    ctxt.push(format!("@.arg.value = {v}"));
+    ctxt.push(format!("@.arg.resume = {resume}"));
+    ctxt.push(String::from("jmp py_yield"));
+
+    ctxt.focus_blk(resume);
+    let sent = ctxt.alloc_irlocal("sent");
+    ctxt.push(format!("{sent} = @.arg"));
+    sent
+}
+
+// `yield from e` yields everything `e` produces, and passes on the values sent to us:
+// None as `__next__()`, anything else as `send(x)`.
+// Evaluates to the value of the StopIteration, that ends `e`.
+pub fn lower_yield_from(e: Lowered, ctxt: &mut Ctxt) -> Lowered {
+    let iter_f = pexpr_load(&PlaceExpr::Attr(e, String::from("__iter__")), ctxt);
+    let it = lower_fn_call(iter_f, Vec::new(), ctxt);
+
+    let sent = ctxt.alloc_irlocal("sent");
+    ctxt.push(format!("{sent} = @.singletons.none"));
+
+    let loop_pid = ctxt.alloc_blk();
+    let call_pid = ctxt.alloc_blk();
+    let next_pid = ctxt.alloc_blk();
+    let done_pid = ctxt.alloc_blk();
+    let value_pid = ctxt.alloc_blk();
+    ctxt.push(format!("jmp {loop_pid}"));
+
+    ctxt.focus_blk(loop_pid);
+        // the end of `it` is signalled by an exception.
+        let h = ctxt.alloc_irlocal("handler");
+        ctxt.push(format!("{h} = {{}}"));
+        ctxt.push(format!("{h}.parent = @.handler"));
+        ctxt.push(format!("{h}.frame = @.frame"));
+        ctxt.push(format!("{h}.pid = {done_pid}"));
+        ctxt.push(format!("@.handler = {h}"));
+
+        let args = ctxt.alloc_irlocal("args");
+        let t = Symbol::new_fresh("jmptab");
+        ctxt.push(format!("{args} = {{}}"));
+        ctxt.push(format!("%{t} = {{}}"));
+        ctxt.push(format!("%{t}[{sent}] = {sent}"));
+        ctxt.push(format!("%{t}[@.singletons.none] = Undef"));
+        ctxt.push(format!("{args}[0] = %{t}[{sent}]"));
+
+        let attr = Symbol::new_fresh("attr");
+        ctxt.push(format!("%{attr} = {{}}"));
+        ctxt.push(format!("%{attr}[{sent}] = \"send\""));
+        ctxt.push(format!("%{attr}[@.singletons.none] = \"__next__\""));
+
+        ctxt.push(String::from("@.arg = {}"));
+        ctxt.push(format!("@.arg.obj = {it}"));
+        ctxt.push(format!("@.arg.attr = %{attr}[{sent}]"));
+        ctxt.push(format!("@.arg.suc = {call_pid}"));
+        ctxt.push(String::from("jmp py_attrlookup"));
+
+    ctxt.focus_blk(call_pid);
+        ctxt.push(String::from("@.arg = {}"));
+        ctxt.push(String::from("@.arg.f = @.ret"));
+        ctxt.push(format!("@.arg.args = {args}"));
+        ctxt.push(format!("@.arg.suc = {next_pid}"));
+        ctxt.push(String::from("jmp py_call"));
+
+    ctxt.focus_blk(next_pid);
+        ctxt.push(String::from("@.handler = @.handler.parent"));
+        let v = ctxt.alloc_irlocal("yield_from_val");
+        ctxt.push(format!("{v} = @.ret"));
+        let s = lower_yield(v, ctxt);
+        ctxt.push(format!("{sent} = {s}"));
+        ctxt.push(format!("jmp {loop_pid}"));
+
+    ctxt.focus_blk(done_pid);
+        // other exceptions are passed on to the enclosing handler.
+        ctxt.push(String::from("@.arg = {}"));
+        ctxt.push(String::from("@.arg.obj = @.exc"));
+        ctxt.push(String::from("@.arg.cls = @.singletons.stop_iteration"));
+        ctxt.push(format!("@.arg.suc_true = {value_pid}"));
+        ctxt.push(String::from("@.arg.suc_false = raise"));
+        ctxt.push(String::from("jmp py_exc_match"));
+
+    ctxt.focus_blk(value_pid);
+        let ret = ctxt.alloc_irlocal("yield_from_ret");
+        ctxt.push(format!("{ret} = @.exc.dict[\"value\"]"));
+        ret
+}
@@ -15,33 +15,47 @@ use pexpr::*;
 mod body;
 use body::*;
 
+mod unwind;
+use unwind::*;
+
+mod generator;
+use generator::*;
+
 type Lowered = String;
 
-pub fn lower(ast: &AST) -> Lowered {
+pub fn lower(ast: &AST) -> Result<Lowered, ParseError> {
     let mut s = String::from("#\n");
-    s.extend(lower_ast(ast).chars());
+    s.extend(lower_ast(ast)?.chars());
     s.extend(include_str!(concat!(env!("OUT_DIR"), "/concat.ir")).chars());
 
-    s
+    Ok(s)
 }
 
-fn lower_ast(ast: &AST) -> Lowered {
-    let nameres_tab = nameres(ast);
+fn lower_ast(ast: &AST) -> Result<Lowered, ParseError> {
+    let NameRes { tab: nameres_tab, generators } = nameres(ast)?;
     let userstart = Symbol::new("userstart".to_string());
     let mut ctxt = Ctxt {
         stack: vec![FnCtxt {
             current_pid: userstart,
             lowering: Some(FnLowerCtxt {
-                loop_stack: Vec::new(),
-                ast_ptr: 0 as *const _,
+                blocks: Vec::new(),
+                except_stack: Vec::new(),
+                ast_ptr: std::ptr::null(),
+                frame_ptr: std::ptr::null(),
             }),
         }],
         nameres_tab,
+        generators,
         procs: Map::new(),
     };
 
     ctxt.procs.insert(userstart, Vec::new());
 
+    if let Some(stmt) = ast.first() {
+        ctxt.push(String::from("@.frame.fn_name = \"<module>\""));
+        ctxt.push(format!("@.frame.file = {}", fmt_str_literal(&stmt.span.file.to_string())));
+    }
+
     lower_body(&**ast, &mut ctxt);
 
     ctxt.push(format!("exit"));
@@ -54,5 +68,5 @@ fn lower_ast(ast: &AST) -> Lowered {
         }
         s.push_str("}\n");
     }
-    s
+    Ok(s)
 }
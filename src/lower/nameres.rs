@@ -1,52 +1,73 @@
 use crate::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VarPlace {
     Global, // unknown: check global module namespace and then builtins.
-    // TODO add
-    // Closured(/*how many scopes out?*/ usize),
     Local,
+    Cell, // a local, that is captured by an inner function. It lives in a cell in `@.frame.cells`.
+    Closured, // a variable of an enclosing function. Its cell is found in `@.frame.closure`.
 }
 
 pub type NameResTable = Map<
     (
-        /*ptr address of ASTStatement::Def(_)*/ *const ASTStatement,
+        /*ptr address of ASTStatementKind::Def(_)*/ *const ASTStatement,
         String,
     ),
     VarPlace,
 >;
 
-pub fn nameres(ast: &AST) -> NameResTable {
+// The scopes enclosing a statement, starting with the module (ptr 0).
+// Class bodies are scopes too, but they are invisible to the functions defined within them.
+// `in loop` is set while resolving a loop body of that scope, where `break` and `continue` are allowed.
+type ScopeStack = Vec<(/*ptr*/ *const ASTStatement, /*is class*/ bool, /*in loop*/ bool)>;
+
+pub struct NameRes {
+    pub tab: NameResTable,
+
+    // the defs containing a `yield`, i.e. the generator functions.
+    pub generators: Set<*const ASTStatement>,
+}
+
+pub fn nameres(ast: &AST) -> Result<NameRes, ParseError> {
     let mut nrt = NameResTable::new();
     iter(ast, &mut nrt, 0 as _);
-    nrt
+
+    // Now that all locals are known, the free variables can be resolved.
+    let mut nr = NameRes { tab: nrt, generators: Set::new() };
+    let mut scopes = vec![(0 as _, false, false)];
+    resolve_body(ast, &mut nr, &mut scopes)?;
+    Ok(nr)
 }
 
 fn iter(ast: &AST, nrt: &mut NameResTable, current_fn_ptr: *const ASTStatement) {
     for stmt in ast {
-        match stmt {
-            ASTStatement::Assign(ASTExpr::Var(v), _) | ASTStatement::AugAssign(ASTExpr::Var(v), _, _) => {
-                let k = (current_fn_ptr, v.to_string());
-                if !nrt.contains_key(&k) {
-                    nrt.insert(k, VarPlace::Local);
+        match &stmt.kind {
+            ASTStatementKind::Assign(targets, _) => {
+                for t in targets {
+                    add_target_locals(t, nrt, current_fn_ptr);
+                }
+            }
+            ASTStatementKind::AugAssign(t, _, _) => add_target_locals(t, nrt, current_fn_ptr),
+            ASTStatementKind::Del(targets) => {
+                for t in targets {
+                    add_target_locals(t, nrt, current_fn_ptr);
                 }
             }
-            ASTStatement::Assign(..) | ASTStatement::AugAssign(..) => {}, // correct?
-            ASTStatement::Def(name, args, body) => {
+            ASTStatementKind::Def(name, params, body) => {
                 let k = (current_fn_ptr, name.to_string());
                 if !nrt.contains_key(&k) {
                     nrt.insert(k, VarPlace::Local);
                 }
 
-                for a in args {
-                    let k = (stmt as _, a.to_string());
+                for p in params {
+                    let k = (stmt as _, p.name.to_string());
                     if !nrt.contains_key(&k) {
                         nrt.insert(k, VarPlace::Local);
                     }
                 }
                 iter(body, nrt, stmt as _);
             }
-            ASTStatement::Class(name, _args, body) => {
+            ASTStatementKind::Class(name, _args, body) => {
                 let k = (current_fn_ptr, name.to_string());
                 if !nrt.contains_key(&k) {
                     nrt.insert(k, VarPlace::Local);
@@ -54,41 +75,301 @@ fn iter(ast: &AST, nrt: &mut NameResTable, current_fn_ptr: *const ASTStatement)
 
                 iter(body, nrt, stmt as _);
             },
-            ASTStatement::If(_, body, else_) | ASTStatement::While(_, body, else_) => {
+            ASTStatementKind::If(_, body, else_) | ASTStatementKind::While(_, body, else_) => {
                 iter(body, nrt, current_fn_ptr);
                 if let Some(else_) = else_ {
                     iter(else_, nrt, current_fn_ptr);
                 }
             }
-            ASTStatement::For(v, _, body) => {
-                let k = (current_fn_ptr, v.to_string());
-                if !nrt.contains_key(&k) {
-                    nrt.insert(k, VarPlace::Local);
-                }
+            ASTStatementKind::For(target, _, body, else_) => {
+                add_target_locals(target, nrt, current_fn_ptr);
                 iter(body, nrt, current_fn_ptr);
+                if let Some(else_) = else_ {
+                    iter(else_, nrt, current_fn_ptr);
+                }
             }
-            ASTStatement::Try(body, excepts) => {
+            ASTStatementKind::Try(body, excepts, else_, finally) => {
                 iter(body, nrt, current_fn_ptr);
                 for except in excepts {
+                    if let Some(name) = &except.name {
+                        let k = (current_fn_ptr, name.to_string());
+                        if !nrt.contains_key(&k) {
+                            nrt.insert(k, VarPlace::Local);
+                        }
+                    }
                     iter(&except.body, nrt, current_fn_ptr);
                 }
+                for b in [else_, finally].into_iter().flatten() {
+                    iter(b, nrt, current_fn_ptr);
+                }
             }
-            ASTStatement::Scope(ScopeKind::Global, vars) => {
+            ASTStatementKind::Scope(ScopeKind::Global, vars) => {
                 for v in vars {
                     nrt.insert((current_fn_ptr, v.to_string()), VarPlace::Global);
                 }
             }
-            ASTStatement::Scope(ScopeKind::NonLocal, vars) => {
+            ASTStatementKind::Scope(ScopeKind::NonLocal, vars) => {
                 for v in vars {
-                    nrt.insert((current_fn_ptr, v.to_string()), todo!());
+                    nrt.insert((current_fn_ptr, v.to_string()), VarPlace::Closured);
                 }
             }
-            ASTStatement::Break
-            | ASTStatement::Continue
-            | ASTStatement::Return(_)
-            | ASTStatement::Expr(_)
-            | ASTStatement::Raise(_)
-            | ASTStatement::Pass => {}
+            ASTStatementKind::Break
+            | ASTStatementKind::Continue
+            | ASTStatementKind::Return(_)
+            | ASTStatementKind::Expr(_)
+            | ASTStatementKind::Raise(_)
+            | ASTStatementKind::Pass => {}
         }
     }
 }
+
+// every variable assigned to by the target `t` becomes local.
+fn add_target_locals(t: &ASTExpr, nrt: &mut NameResTable, current_fn_ptr: *const ASTStatement) {
+    match &t.kind {
+        ASTExprKind::Var(v) => {
+            let k = (current_fn_ptr, v.to_string());
+            if !nrt.contains_key(&k) {
+                nrt.insert(k, VarPlace::Local);
+            }
+        },
+        ASTExprKind::Tuple(elems) | ASTExprKind::List(elems) => {
+            for e in elems {
+                add_target_locals(e, nrt, current_fn_ptr);
+            }
+        },
+        _ => {}, // attributes and subscripts don't introduce variables.
+    }
+}
+
+fn resolve_body(ast: &AST, nr: &mut NameRes, scopes: &mut ScopeStack) -> Result<(), ParseError> {
+    for stmt in ast {
+        match &stmt.kind {
+            ASTStatementKind::Assign(targets, e) => {
+                for t in targets {
+                    resolve_expr(t, nr, scopes)?;
+                }
+                resolve_expr(e, nr, scopes)?;
+            }
+            ASTStatementKind::AugAssign(t, _, e) => {
+                resolve_expr(t, nr, scopes)?;
+                resolve_expr(e, nr, scopes)?;
+            }
+            ASTStatementKind::Del(targets) => {
+                for t in targets {
+                    resolve_expr(t, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::Def(_, params, body) => {
+                // defaults are evaluated in the enclosing scope.
+                for p in params {
+                    if let Some(d) = &p.default {
+                        resolve_expr(d, nr, scopes)?;
+                    }
+                }
+                scopes.push((stmt as _, false, false));
+                resolve_body(body, nr, scopes)?;
+                scopes.pop();
+            }
+            ASTStatementKind::Class(_, bases, body) => {
+                for b in bases {
+                    resolve_expr(b, nr, scopes)?;
+                }
+                scopes.push((stmt as _, true, false));
+                resolve_body(body, nr, scopes)?;
+                scopes.pop();
+            }
+            ASTStatementKind::If(cond, body, else_) => {
+                resolve_expr(cond, nr, scopes)?;
+                resolve_body(body, nr, scopes)?;
+                if let Some(else_) = else_ {
+                    resolve_body(else_, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::While(cond, body, else_) => {
+                resolve_expr(cond, nr, scopes)?;
+                resolve_loop_body(body, nr, scopes)?;
+                if let Some(else_) = else_ {
+                    resolve_body(else_, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::For(target, e, body, else_) => {
+                resolve_expr(target, nr, scopes)?;
+                resolve_expr(e, nr, scopes)?;
+                resolve_loop_body(body, nr, scopes)?;
+                if let Some(else_) = else_ {
+                    resolve_body(else_, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::Try(body, excepts, else_, finally) => {
+                resolve_body(body, nr, scopes)?;
+                for except in excepts {
+                    if let Some(ty) = &except.ty {
+                        resolve_expr(ty, nr, scopes)?;
+                    }
+                    resolve_body(&except.body, nr, scopes)?;
+                }
+                for b in [else_, finally].into_iter().flatten() {
+                    resolve_body(b, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::Scope(ScopeKind::NonLocal, vars) => {
+                if scopes.len() == 1 {
+                    let msg = String::from("nonlocal declaration not allowed at module level");
+                    return Err(ParseError { msg, span: stmt.span });
+                }
+                for v in vars {
+                    resolve_var(v, stmt.span, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::Return(e) => {
+                let (_, is_class, _) = *scopes.last().unwrap();
+                if scopes.len() == 1 || is_class {
+                    let msg = String::from("'return' outside function");
+                    return Err(ParseError { msg, span: stmt.span });
+                }
+                if let Some(e) = e {
+                    resolve_expr(e, nr, scopes)?;
+                }
+            }
+            ASTStatementKind::Break | ASTStatementKind::Continue => {
+                let (_, _, in_loop) = *scopes.last().unwrap();
+                if !in_loop {
+                    let msg = match stmt.kind {
+                        ASTStatementKind::Break => "'break' outside loop",
+                        _ => "'continue' not properly in loop",
+                    };
+                    return Err(ParseError { msg: msg.to_string(), span: stmt.span });
+                }
+            }
+            ASTStatementKind::Expr(e) | ASTStatementKind::Raise(Some(e)) => {
+                resolve_expr(e, nr, scopes)?;
+            }
+            ASTStatementKind::Scope(ScopeKind::Global, _)
+            | ASTStatementKind::Raise(None)
+            | ASTStatementKind::Pass => {}
+        }
+    }
+    Ok(())
+}
+
+fn resolve_loop_body(ast: &AST, nr: &mut NameRes, scopes: &mut ScopeStack) -> Result<(), ParseError> {
+    let in_loop = std::mem::replace(&mut scopes.last_mut().unwrap().2, true);
+    let res = resolve_body(ast, nr, scopes);
+    scopes.last_mut().unwrap().2 = in_loop;
+    res
+}
+
+fn resolve_expr(e: &ASTExpr, nr: &mut NameRes, scopes: &mut ScopeStack) -> Result<(), ParseError> {
+    match &e.kind {
+        ASTExprKind::Var(v) => resolve_var(v, e.span, nr, scopes)?,
+        ASTExprKind::List(elems) | ASTExprKind::Tuple(elems) => {
+            for x in elems {
+                resolve_expr(x, nr, scopes)?;
+            }
+        },
+        ASTExprKind::Dict(items) => {
+            for (k, v) in items {
+                resolve_expr(k, nr, scopes)?;
+                resolve_expr(v, nr, scopes)?;
+            }
+        },
+        ASTExprKind::FnCall(f, args) => {
+            resolve_expr(f, nr, scopes)?;
+            for a in args {
+                match a {
+                    ASTArg::Positional(x) | ASTArg::Keyword(_, x) | ASTArg::Star(x) | ASTArg::DoubleStar(x) => resolve_expr(x, nr, scopes)?,
+                }
+            }
+        },
+        ASTExprKind::BinOp(_, l, r) => {
+            resolve_expr(l, nr, scopes)?;
+            resolve_expr(r, nr, scopes)?;
+        },
+        ASTExprKind::UnOp(_, x) | ASTExprKind::Attribute(x, _) => resolve_expr(x, nr, scopes)?,
+        ASTExprKind::IfExpr(cond, then, else_) => {
+            resolve_expr(then, nr, scopes)?;
+            resolve_expr(cond, nr, scopes)?;
+            resolve_expr(else_, nr, scopes)?;
+        },
+        ASTExprKind::Compare(first, rest) => {
+            resolve_expr(first, nr, scopes)?;
+            for (_, x) in rest {
+                resolve_expr(x, nr, scopes)?;
+            }
+        },
+        ASTExprKind::Slice(b) => {
+            let (x, y, z) = &**b;
+            for x in [x, y, z].into_iter().flatten() {
+                resolve_expr(x, nr, scopes)?;
+            }
+        },
+        ASTExprKind::Yield(_) | ASTExprKind::YieldFrom(_) => {
+            let (ptr, is_class, _) = *scopes.last().unwrap();
+            if scopes.len() == 1 || is_class {
+                let msg = String::from("'yield' outside function");
+                return Err(ParseError { msg, span: e.span });
+            }
+            nr.generators.insert(ptr);
+            match &e.kind {
+                ASTExprKind::Yield(Some(x)) | ASTExprKind::YieldFrom(x) => resolve_expr(x, nr, scopes)?,
+                _ => {},
+            }
+        },
+        ASTExprKind::Str(_) | ASTExprKind::Int(_) | ASTExprKind::Float(_) | ASTExprKind::Bool(_) | ASTExprKind::None => {},
+    }
+    Ok(())
+}
+
+// Looks up `v` in the enclosing functions, if it's not bound in the innermost scope.
+// The function defining `v` turns it into a Cell, and every function in between gets it Closured,
+// so that the cell can be passed down through their closures.
+fn resolve_var(v: &str, span: Span, nr: &mut NameRes, scopes: &ScopeStack) -> Result<(), ParseError> {
+    let (ptr, is_class, _) = *scopes.last().unwrap();
+    let k = (ptr, v.to_string());
+    let nonlocal = match nr.tab.get(&k) {
+        None => false,
+        Some(VarPlace::Closured) => true,
+        Some(_) => return Ok(()),
+    };
+
+    let mut found = None;
+    for (i, (p, class, _)) in scopes.iter().enumerate().rev().skip(1) {
+        if *class { continue; }
+        if i == 0 { break; } // module-level variables are globals.
+        match nr.tab.get(&(*p, v.to_string())) {
+            Some(VarPlace::Global) => break,
+            Some(_) => {
+                found = Some(i);
+                break;
+            },
+            None => {},
+        }
+    }
+
+    let Some(def) = found else {
+        if nonlocal {
+            let msg = format!("no binding for nonlocal '{v}' found");
+            return Err(ParseError { msg, span });
+        }
+        return Ok(());
+    };
+
+    let def_k = (scopes[def].0, v.to_string());
+    if nr.tab[&def_k] == VarPlace::Local {
+        nr.tab.insert(def_k, VarPlace::Cell);
+    }
+    for (p, class, _) in &scopes[def+1..] {
+        if !class {
+            nr.tab.insert((*p, v.to_string()), VarPlace::Closured);
+        }
+    }
+
+    // class bodies run in the frame of their enclosing function, so they access the variable the same way.
+    if is_class {
+        let frame = scopes.iter().rposition(|(_, class, _)| !class).unwrap();
+        let place = nr.tab[&(scopes[frame].0, v.to_string())];
+        nr.tab.insert(k, place);
+    }
+
+    Ok(())
+}
@@ -5,29 +5,82 @@ pub enum PlaceExpr {
     Subscript(Lowered, Lowered), // lhs[i]
     Attr(Lowered, String), // lhs.attr
     Var(String), // v
+    Tuple(Vec<PlaceExpr>), // (a, b) or [a, b]
 }
 
 pub fn lower_pexpr(e: &ASTExpr, ctxt: &mut Ctxt) -> PlaceExpr {
-    match e {
-        ASTExpr::Var(v) => PlaceExpr::Var(v.clone()),
-        ASTExpr::Attribute(e, v) => {
+    match &e.kind {
+        ASTExprKind::Var(v) => PlaceExpr::Var(v.clone()),
+        ASTExprKind::Attribute(e, v) => {
             let e = lower_expr(e, ctxt);
             PlaceExpr::Attr(e, v.to_string())
         },
-        ASTExpr::BinOp(ASTBinOpKind::Subscript, e, i) => {
+        ASTExprKind::BinOp(ASTBinOpKind::Subscript, e, i) => {
             let e = lower_expr(e, ctxt);
             let i = lower_expr(i, ctxt);
             PlaceExpr::Subscript(e, i)
         },
-        _ => panic!("Not a PlaceExpr: {e:?}"),
+        ASTExprKind::Tuple(elems) | ASTExprKind::List(elems) => {
+            PlaceExpr::Tuple(elems.iter().map(|e| lower_pexpr(e, ctxt)).collect())
+        },
+        _ => unreachable!("assignment targets are checked by the parser: {e:?}"),
     }
 }
 
+// `target = val`, where the subexpressions of each target are only evaluated right before it's stored to.
+// e.g. in `i, a[i] = 1, 5` the index `i` is already 1.
+pub fn lower_assign(target: &ASTExpr, val: Lowered, ctxt: &mut Ctxt) {
+    let (ASTExprKind::Tuple(elems) | ASTExprKind::List(elems)) = &target.kind else {
+        let target = lower_pexpr(target, ctxt);
+        pexpr_store(&target, val, ctxt);
+        return;
+    };
+
+    let unpacked = lower_unpack(val, elems.len(), ctxt);
+    for (i, e) in elems.iter().enumerate() {
+        lower_assign(e, format!("{unpacked}[{i}]"), ctxt);
+    }
+}
+
+// unpacks `val` into `n` values, see py_unpack.
+fn lower_unpack(val: Lowered, n: usize, ctxt: &mut Ctxt) -> Lowered {
+    let suc = ctxt.alloc_blk();
+    let arg = Symbol::new_fresh("arg");
+    ctxt.push(format!("%{arg} = {{}}"));
+    ctxt.push(format!("%{arg}.obj = {val}"));
+    ctxt.push(format!("%{arg}.n = {n}"));
+    ctxt.push(format!("%{arg}.suc = {suc}"));
+    ctxt.push(format!("@.arg = %{arg}"));
+    ctxt.push(String::from("jmp py_unpack"));
+
+    ctxt.focus_blk(suc);
+    let unpacked = ctxt.alloc_irlocal("unpacked");
+    ctxt.push(format!("{unpacked} = @.ret"));
+    unpacked
+}
+
 pub fn pexpr_load(e: &PlaceExpr, ctxt: &mut Ctxt) -> Lowered {
     match e {
         PlaceExpr::Var(var) => {
-            let ns = find_namespace(var, ctxt);
-            format!("{ns}[\"{var}\"]")
+            let v = find_var(var, ctxt);
+
+            // reading an unbound variable raises NameError.
+            let suc = ctxt.alloc_blk();
+            let err = ctxt.alloc_blk();
+            let t = Symbol::new_fresh("jmptab");
+            ctxt.push(format!("%{t} = {{}}"));
+            ctxt.push(format!("%{t}[{v}] = {suc}"));
+            ctxt.push(format!("%{t}[Undef] = {err}"));
+            ctxt.push(format!("jmp %{t}[{v}]"));
+
+            ctxt.focus_blk(err);
+                ctxt.push(String::from("@.arg = {}"));
+                ctxt.push(String::from("@.arg.cls = @.singletons.name_error"));
+                ctxt.push(format!("@.arg.msg = \"name '{var}' is not defined\""));
+                ctxt.push(String::from("jmp py_raise_error"));
+
+            ctxt.focus_blk(suc);
+                v
         },
         PlaceExpr::Attr(e, a) => {
             let suc = ctxt.alloc_blk();
@@ -47,6 +100,30 @@ pub fn pexpr_load(e: &PlaceExpr, ctxt: &mut Ctxt) -> Lowered {
             let i = i.to_string();
             lower_binop(ASTBinOpKind::Subscript, e, i, ctxt)
         },
+        PlaceExpr::Tuple(_) => unreachable!("tuple targets are never loaded"),
+    }
+}
+
+pub fn pexpr_delete(e: &PlaceExpr, ctxt: &mut Ctxt) {
+    match e {
+        PlaceExpr::Var(var) => {
+            let v = find_var(var, ctxt);
+            ctxt.push(format!("{v} = Undef"));
+        },
+        PlaceExpr::Attr(e, v) => {
+            ctxt.push(format!("{e}.dict[\"{v}\"] = Undef"));
+        },
+        PlaceExpr::Subscript(e, i) => {
+            let e = e.to_string();
+            let i = i.to_string();
+            let f = pexpr_load(&PlaceExpr::Attr(e, String::from("__delitem__")), ctxt);
+            lower_fn_call(f, vec![i], ctxt);
+        },
+        PlaceExpr::Tuple(elems) => {
+            for e in elems {
+                pexpr_delete(e, ctxt);
+            }
+        },
     }
 }
 
@@ -64,6 +141,12 @@ pub fn pexpr_store(e: &PlaceExpr, val: Lowered, ctxt: &mut Ctxt) {
             let f = pexpr_load(&PlaceExpr::Attr(e, String::from("__setitem__")), ctxt);
             lower_fn_call(f, vec![i, val], ctxt);
         },
+        PlaceExpr::Tuple(elems) => {
+            let unpacked = lower_unpack(val, elems.len(), ctxt);
+            for (i, e) in elems.iter().enumerate() {
+                pexpr_store(e, format!("{unpacked}[{i}]"), ctxt);
+            }
+        },
     }
 }
 
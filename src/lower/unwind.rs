@@ -0,0 +1,230 @@
+use crate::lower::*;
+
+// a way of leaving the current statement, other than raising.
+pub(in crate::lower) enum Exit {
+    Return(Lowered),
+    Jump(/*break or continue target*/ Symbol),
+}
+
+// Leaves the enclosing blocks up to the target of `exit`.
+// If we pass a finally block, we only run its body; the rest of the exit is done by the continuation pid, that the finally block lowers once its body is done.
+pub(in crate::lower) fn lower_exit(exit: Exit, ctxt: &mut Ctxt) {
+    let idx = ctxt.fl().blocks.iter().rposition(|b| match b {
+        Block::Finally(_) => true,
+        Block::Loop { brk, cont } => matches!(exit, Exit::Jump(t) if t == *brk || t == *cont),
+        Block::Handler(_) => false,
+    });
+
+    let fb = match idx.map(|i| &ctxt.fl().blocks[i]) {
+        Some(Block::Finally(fb)) => fb,
+        _ => {
+            // the handlers we leave are uninstalled by restoring the parent of the outermost one.
+            let start = idx.map_or(0, |i| i + 1);
+            let outermost = ctxt.fl().blocks[start..].iter().find_map(|b| match b {
+                Block::Handler(h) => Some(h.clone()),
+                _ => None,
+            });
+            if let Some(h) = outermost {
+                ctxt.push(format!("@.handler = {h}.parent"));
+            }
+
+            match exit {
+                Exit::Return(v) => {
+                    ctxt.push(format!("@.ret = {v}"));
+                    ctxt.push(String::from("jmp pop_stack"));
+                },
+                Exit::Jump(t) => ctxt.push(format!("jmp {t}")),
+            }
+            return;
+        },
+    };
+    let pending = fb.pending.clone();
+    let handler = fb.handler.clone();
+    let body_pid = fb.body_pid;
+    let cont = match &exit {
+        Exit::Return(_) => fb.return_pid,
+        Exit::Jump(t) => fb.jump_pids.get(t).copied(),
+    };
+
+    let cont = cont.unwrap_or_else(|| ctxt.alloc_blk());
+    let Some(Block::Finally(fb)) = idx.map(|i| &mut ctxt.fl_mut().blocks[i]) else { unreachable!() };
+    match &exit {
+        Exit::Return(_) => fb.return_pid = Some(cont),
+        Exit::Jump(t) => { fb.jump_pids.insert(*t, cont); },
+    }
+
+    if let Exit::Return(v) = exit {
+        ctxt.push(format!("{pending}.value = {v}"));
+    }
+
+    // restores the handler stack from before the try statement.
+    ctxt.push(format!("@.handler = {handler}.parent"));
+    ctxt.push(format!("{pending}.action = {cont}"));
+    ctxt.push(format!("jmp {body_pid}"));
+}
+
+pub(in crate::lower) fn lower_try(body: &[ASTStatement], excepts: &[Except], else_: Option<&[ASTStatement]>, finally: Option<&[ASTStatement]>, ctxt: &mut Ctxt) {
+    let Some(finally) = finally else {
+        lower_try_except(body, excepts, else_, ctxt);
+        return;
+    };
+
+    let pending = ctxt.alloc_irlocal("pending");
+    let h = ctxt.alloc_irlocal("handler");
+    let exc_pid = ctxt.alloc_blk();
+    let fin_pid = ctxt.alloc_blk();
+    let suc = ctxt.alloc_blk();
+
+    ctxt.push(format!("{pending} = {{}}"));
+
+    // push handler stack
+    ctxt.push(format!("{h} = {{}}"));
+    ctxt.push(format!("{h}.parent = @.handler"));
+    ctxt.push(format!("{h}.frame = @.frame"));
+    ctxt.push(format!("{h}.pid = {exc_pid}"));
+    ctxt.push(format!("@.handler = {h}"));
+
+    ctxt.fl_mut().blocks.push(Block::Finally(FinallyBlock {
+        pending: pending.clone(),
+        handler: h.clone(),
+        body_pid: fin_pid,
+        return_pid: None,
+        jump_pids: Map::new(),
+    }));
+    lower_try_except(body, excepts, else_, ctxt);
+    let Some(Block::Finally(fb)) = ctxt.fl_mut().blocks.pop() else { unreachable!() };
+
+    // pop handler stack
+    ctxt.push(format!("@.handler = {h}.parent"));
+    ctxt.push(format!("{pending}.action = {suc}"));
+    ctxt.push(format!("jmp {fin_pid}"));
+
+    // the exception is re-raised after the finally body.
+    let reraise = ctxt.alloc_blk();
+    ctxt.focus_blk(exc_pid);
+    ctxt.push(format!("{pending}.exc = @.exc"));
+    ctxt.push(format!("{pending}.action = {reraise}"));
+    ctxt.push(format!("jmp {fin_pid}"));
+
+    // the finally body might have overwritten @.exc.
+    ctxt.focus_blk(reraise);
+    ctxt.push(format!("@.exc = {pending}.exc"));
+    ctxt.push(String::from("jmp raise"));
+
+    ctxt.focus_blk(fin_pid);
+    lower_body(finally, ctxt);
+    ctxt.push(format!("jmp {pending}.action"));
+
+    // continue the exits that were interrupted by the finally body.
+    if let Some(pid) = fb.return_pid {
+        ctxt.focus_blk(pid);
+        lower_exit(Exit::Return(format!("{pending}.value")), ctxt);
+    }
+    for (target, pid) in fb.jump_pids {
+        ctxt.focus_blk(pid);
+        lower_exit(Exit::Jump(target), ctxt);
+    }
+
+    ctxt.focus_blk(suc);
+}
+
+fn lower_try_except(body: &[ASTStatement], excepts: &[Except], else_: Option<&[ASTStatement]>, ctxt: &mut Ctxt) {
+    let suc = ctxt.alloc_blk();
+
+    let mut pids = Vec::new();
+    let mut handlers = Vec::new();
+
+    // We push to the handler stack in reverted order, as the first `except` should be at the top of the stack.
+    // If an except clause doesn't match, it re-raises to the handler of the next one.
+    for _except in excepts.iter().rev() {
+        let h = ctxt.alloc_irlocal("handler");
+        let except_pid = ctxt.alloc_blk();
+        pids.push(except_pid);
+        handlers.push(h.clone());
+
+        // push handler stack
+        ctxt.push(format!("{h} = {{}}"));
+        ctxt.push(format!("{h}.parent = @.handler"));
+        ctxt.push(format!("{h}.frame = @.frame"));
+        ctxt.push(format!("{h}.pid = {except_pid}"));
+        ctxt.push(format!("@.handler = {h}"));
+    }
+
+    if let Some(h) = handlers.first() {
+        ctxt.fl_mut().blocks.push(Block::Handler(h.clone()));
+    }
+    lower_body(body, ctxt);
+    if !handlers.is_empty() {
+        ctxt.fl_mut().blocks.pop();
+    }
+
+    // pop handler stack
+    for _except in excepts.iter() {
+        ctxt.push(String::from("@.handler = @.handler.parent"));
+    }
+
+    // exceptions from the else body are not handled by the except clauses.
+    if let Some(else_) = else_ {
+        lower_body(else_, ctxt);
+    }
+
+    ctxt.push(format!("jmp {suc}"));
+
+    pids.reverse();
+    for (i, (pid, except)) in pids.iter().zip(excepts.iter()).enumerate() {
+        ctxt.focus_blk(*pid);
+
+        let exc = ctxt.alloc_irlocal("exc");
+        ctxt.push(format!("{exc} = @.exc"));
+
+        if let Some(ty) = &except.ty {
+            let ty = lower_expr(ty, ctxt);
+            let matched = ctxt.alloc_blk();
+            let unmatched = ctxt.alloc_blk();
+
+            ctxt.push(String::from("@.arg = {}"));
+            ctxt.push(format!("@.arg.obj = {exc}"));
+            ctxt.push(format!("@.arg.cls = {ty}"));
+            ctxt.push(format!("@.arg.suc_true = {matched}"));
+            ctxt.push(format!("@.arg.suc_false = {unmatched}"));
+            ctxt.push(String::from("jmp py_exc_match"));
+
+            // evaluating the type might have overwritten @.exc.
+            ctxt.focus_blk(unmatched);
+            ctxt.push(format!("@.exc = {exc}"));
+            ctxt.push(String::from("jmp raise"));
+
+            ctxt.focus_blk(matched);
+        }
+
+        // the handlers of the following except clauses are still installed.
+        for _ in i+1..excepts.len() {
+            ctxt.push(String::from("@.handler = @.handler.parent"));
+        }
+
+        if let Some(name) = &except.name {
+            lower_var_assign(name, exc.clone(), ctxt);
+        }
+
+        ctxt.fl_mut().except_stack.push(exc);
+        lower_body(&except.body, ctxt);
+        ctxt.fl_mut().except_stack.pop();
+
+        // like python, the name is unbound once the handler is done.
+        if let Some(name) = &except.name {
+            lower_var_assign(name, String::from("Undef"), ctxt);
+        }
+
+        // the exception is handled, a bare raise afterwards has nothing to re-raise.
+        match ctxt.fl().except_stack.last() {
+            Some(outer) => {
+                let outer = outer.clone();
+                ctxt.push(format!("@.exc = {outer}"));
+            },
+            None => ctxt.push(String::from("@.exc = Undef")),
+        }
+        ctxt.push(format!("jmp {suc}"));
+    }
+
+    ctxt.focus_blk(suc);
+}
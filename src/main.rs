@@ -8,6 +8,8 @@ pub use indexmap::IndexSet as Set;
 pub use std::hash::Hash;
 
 pub use noisy_float::prelude::{Float, R64};
+pub use num_bigint::BigInt;
+pub use num_traits::{Zero, Signed, ToPrimitive};
 
 mod ast;
 pub use ast::*;
@@ -31,20 +33,21 @@ fn main() {
     let cli = cli();
     let contents = fs::read_to_string(&cli.filename).unwrap();
     let ir_string = if !cli.filename.ends_with(".ir") {
-        let toks = tokenize(&contents);
+        let file = Symbol::new(cli.filename.clone());
+        let toks = tokenize(&contents, file).unwrap_or_else(|e| parse_failure(e, &contents));
         if let Action::ShowTokens = cli.action {
             println!("{:?}", toks);
             return;
         }
 
-        let ast = assemble(&toks);
+        let ast = assemble(&toks).unwrap_or_else(|e| parse_failure(e, &contents));
 
         if let Action::ShowAst = cli.action {
             println!("{:?}", ast);
             return;
         }
 
-        let ir_string = lower(&ast);
+        let ir_string = lower(&ast).unwrap_or_else(|e| parse_failure(e, &contents));
         if let Action::ShowIR = cli.action {
             println!("{}", ir_string);
             return;
@@ -74,3 +77,8 @@ fn main() {
         _ => {},
     }
 }
+
+fn parse_failure(e: ParseError, src: &str) -> ! {
+    eprintln!("{}", e.render(src));
+    std::process::exit(1);
+}
@@ -9,20 +9,73 @@ pub fn step_binop(kind: BinOpKind, l: ValueParticle, r: ValueParticle, mut st: T
     let boolify = |b: bool| if b { true_.clone() } else { false_.clone() };
     let deref = &st.deref;
 
+    // int and float mixed, comparisons are exact. Otherwise the int gets converted.
+    match (kind, &l, &r) {
+        (Lt | Le | Gt | Ge, Int(l), Float(r)) => return (boolify(cmp_holds(kind, cmp_int_float(&(*l).into(), *r))), st),
+        (Lt | Le | Gt | Ge, Float(l), Int(r)) => return (boolify(cmp_holds(kind, cmp_int_float(&(*r).into(), *l).reverse())), st),
+        _ => {},
+    }
+    let (l, r) = match (l, r) {
+        (Int(l), Float(r)) => (Float(R64::new(l as f64)), Float(r)),
+        (Float(l), Int(r)) => (Float(l), Float(R64::new(r as f64))),
+        (l, r) => (l, r),
+    };
+
     let out = match (kind, l, r) {
-        (Plus, Int(l), Int(r)) => Int(l + r),
-        (Minus, Int(l), Int(r)) => Int(l - r),
-        (Mul, Int(l), Int(r)) => Int(l * r),
-        (Div, Int(l), Int(r)) => Int(l / r),
-        (Mod, Int(l), Int(r)) => Int(l % r),
-        (Pow, Int(l), Int(r)) => Int(l.pow(r as _)),
+        // results that don't fit into an i64, and zero divisors (which crash exec), are handled by the general case below.
+        (Plus, Int(l), Int(r)) if let Some(x) = l.checked_add(r) => Int(x),
+        (Minus, Int(l), Int(r)) if let Some(x) = l.checked_sub(r) => Int(x),
+        (Mul, Int(l), Int(r)) if let Some(x) = l.checked_mul(r) => Int(x),
+        (Div, Int(l), Int(r)) if r != 0 && let Some(x) = py_floordiv(&l.into(), &r.into()).to_i64() => Int(x),
+        (Mod, Int(l), Int(r)) if r != 0 && let Some(x) = py_mod(&l.into(), &r.into()).to_i64() => Int(x),
+        (Pow, Int(l), Int(r)) if r < 0 && let Some(x) = finite((l as f64).powf(r as f64)) => Float(x),
+        (Pow, Int(l), Int(r)) if let Some(x) = u32::try_from(r).ok().and_then(|r| l.checked_pow(r)) => Int(x),
+        (Shl, Int(l), Int(r)) if r >= 0 && let Some(x) = py_shl(l, r) => Int(x),
+        (Shr, Int(l), Int(r)) if r >= 0 => Int(py_shr(l, r)),
+        (BitAnd, Int(l), Int(r)) => Int(l & r),
+        (BitOr, Int(l), Int(r)) => Int(l | r),
+        (BitXor, Int(l), Int(r)) => Int(l ^ r),
         (Lt, Int(l), Int(r)) => boolify(l < r),
         (Le, Int(l), Int(r)) => boolify(l <= r),
         (Gt, Int(l), Int(r)) => boolify(l > r),
         (Ge, Int(l), Int(r)) => boolify(l >= r),
-        (kind, l, r) if l.overlaps(&TopInt, deref) && r.overlaps(&TopInt, deref) => {
+
+        (Plus, Float(l), Float(r)) if let Some(x) = finite(l.raw() + r.raw()) => Float(x),
+        (Minus, Float(l), Float(r)) if let Some(x) = finite(l.raw() - r.raw()) => Float(x),
+        (Mul, Float(l), Float(r)) if let Some(x) = finite(l.raw() * r.raw()) => Float(x),
+        (Div, Float(l), Float(r)) if r != 0.0 && let Some(x) = finite(l.raw() / r.raw()) => Float(x),
+        (Mod, Float(l), Float(r)) if r != 0.0 => Float(py_fmod(l, r)),
+        (Pow, Float(l), Float(r)) if let Some(x) = finite(l.raw().powf(r.raw())) => Float(x),
+        (Lt, Float(l), Float(r)) => boolify(l < r),
+        (Le, Float(l), Float(r)) => boolify(l <= r),
+        (Gt, Float(l), Float(r)) => boolify(l > r),
+        (Ge, Float(l), Float(r)) => boolify(l >= r),
+
+        (Plus, String(l), String(r)) => String(format!("{l}{r}")),
+        (Lt, String(l), String(r)) => boolify(l < r),
+        (Le, String(l), String(r)) => boolify(l <= r),
+        (Gt, String(l), String(r)) => boolify(l > r),
+        (Ge, String(l), String(r)) => boolify(l >= r),
+        (Lt|Le|Gt|Ge, l, r) if l.overlaps(&TopString, deref) && r.overlaps(&TopString, deref) => {
+            let vid = crate::merger_analysis::ValueId(crate::symbol::Symbol::new("binopVID"));
+            st.deref.insert(vid, ValueSet(vec![true_, false_]));
+            ValueId(vid)
+        },
+        (Plus, l, r) if l.overlaps(&TopString, deref) && r.overlaps(&TopString, deref) => {
+            let vid = crate::merger_analysis::ValueId(crate::symbol::Symbol::new("binopVID"));
+            st.deref.insert(vid, ValueSet(vec![TopString]));
+            ValueId(vid)
+        },
+
+        (kind, l, r) if is_num(&l, deref) && is_num(&r, deref) => {
             let vs = if matches!(kind, Lt|Le|Gt|Ge) {
                 ValueSet(vec![true_, false_])
+            } else if l.overlaps(&TopFloat, deref) || r.overlaps(&TopFloat, deref) {
+                // floats that aren't finite are Undef.
+                ValueSet(vec![TopFloat, Symbol(crate::symbol::Symbol::new("Undef"))])
+            } else if kind == Pow {
+                // negative exponents give a float.
+                ValueSet(vec![TopInt, TopFloat, Symbol(crate::symbol::Symbol::new("Undef"))])
             } else {
                 ValueSet(vec![TopInt])
             };
@@ -35,3 +88,7 @@ pub fn step_binop(kind: BinOpKind, l: ValueParticle, r: ValueParticle, mut st: T
     (out, st)
 }
 
+
+fn is_num(x: &ValueParticle, deref: &Deref) -> bool {
+    x.overlaps(&ValueParticle::TopInt, deref) || x.overlaps(&ValueParticle::TopFloat, deref)
+}
@@ -9,6 +9,8 @@ impl Debug for ValueParticle {
             ValueParticle::TopString => write!(f, "TopString"),
             ValueParticle::Int(i) => write!(f, "{i}"),
             ValueParticle::TopInt => write!(f, "TopInt"),
+            ValueParticle::Float(x) => write!(f, "{}", fmt_float_literal(*x)),
+            ValueParticle::TopFloat => write!(f, "TopFloat"),
             ValueParticle::TableSort(TableSortId(ts)) => write!(f, "TS:{ts}"),
             ValueParticle::ValueId(ValueId(i)) => write!(f, "VID:{i}"),
             ValueParticle::Top => write!(f, "Top"),
@@ -119,6 +119,8 @@ fn has_free_symbol(vs: &ValueSet) -> bool {
             | ValueParticle::String(_)
             | ValueParticle::TopInt
             | ValueParticle::Int(_)
+            | ValueParticle::TopFloat
+            | ValueParticle::Float(_)
             | ValueParticle::Symbol(_) => return true,
 
             ValueParticle::TableSort(_)
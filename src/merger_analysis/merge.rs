@@ -6,6 +6,7 @@ enum ValueGroup {
     Symbol(Symbol),
     TopString,
     TopInt,
+    TopFloat,
     Top, // Top is not orthogonal to the other groups, but that's okay as it's just a heuristic.
 }
 
@@ -107,6 +108,7 @@ fn groupify(p: &ValueParticle) -> ValueGroup {
         ValueParticle::Symbol(s) => ValueGroup::Symbol(*s),
         ValueParticle::TopString | ValueParticle::String(_) => ValueGroup::TopString,
         ValueParticle::TopInt | ValueParticle::Int(_) => ValueGroup::TopInt,
+        ValueParticle::TopFloat | ValueParticle::Float(_) => ValueGroup::TopFloat,
         ValueParticle::TableSort(tid) => ValueGroup::Table(*tid),
         ValueParticle::ValueId(_) => unreachable!(),
     }
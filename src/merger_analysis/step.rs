@@ -58,10 +58,91 @@ fn step_expr(mut st: ThreadState, expr: &Expr) -> (ValueParticle, ThreadState) {
             st.deref.insert(value_id, vs);
             (ValueParticle::ValueId(value_id), st)
         },
+        Expr::Repr(n) => match st.nodes[n].clone() {
+            ValueParticle::Int(i) => (ValueParticle::String(i.to_string()), st),
+            ValueParticle::Float(x) => (ValueParticle::String(py_float_repr(x)), st),
+            ValueParticle::String(s) => (ValueParticle::String(py_str_repr(&s)), st),
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("reprVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopString]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::StrLen(n) => match st.nodes[n].clone() {
+            ValueParticle::String(s) => (ValueParticle::Int(s.chars().count() as i64), st),
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("strlenVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopInt]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::Substr(s, i, j) => match (st.nodes[s].clone(), st.nodes[i].clone(), st.nodes[j].clone()) {
+            (ValueParticle::String(s), ValueParticle::Int(i), ValueParticle::Int(j)) => (ValueParticle::String(py_substr(&s, &i.into(), &j.into())), st),
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("substrVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopString]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::ParseInt(n) => match st.nodes[n].clone() {
+            ValueParticle::String(s) => match py_parse_int(&s).map(|i| i.to_i64()) {
+                Some(Some(i)) => (ValueParticle::Int(i), st),
+                None => (ValueParticle::Symbol(Symbol::new("Undef")), st),
+                // ints that don't fit into an i64 are not tracked precisely.
+                Some(None) => {
+                    let value_id = ValueId(Symbol::new_fresh("intVID"));
+                    st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopInt]));
+                    (ValueParticle::ValueId(value_id), st)
+                },
+            },
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("parseintVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopInt, ValueParticle::Symbol(Symbol::new("Undef"))]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::ParseFloat(n) => match st.nodes[n].clone() {
+            ValueParticle::String(s) => match py_parse_float(&s) {
+                Some(x) => (ValueParticle::Float(x), st),
+                None => (ValueParticle::Symbol(Symbol::new("Undef")), st),
+            },
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("parsefloatVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopFloat, ValueParticle::Symbol(Symbol::new("Undef"))]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::TrueDiv(l, r) => match (st.nodes[l].clone(), st.nodes[r].clone()) {
+            (ValueParticle::Int(l), ValueParticle::Int(r)) if r != 0 => match py_truediv(&l.into(), &r.into()) {
+                Some(x) => (ValueParticle::Float(x), st),
+                None => (ValueParticle::Symbol(Symbol::new("Undef")), st),
+            },
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("truedivVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopFloat, ValueParticle::Symbol(Symbol::new("Undef"))]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
+        Expr::Trunc(n) => match st.nodes[n].clone() {
+            ValueParticle::Float(x) if let Some(i) = py_trunc(x).to_i64() => (ValueParticle::Int(i), st),
+            _ => {
+                let value_id = ValueId(Symbol::new_fresh("truncVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopInt]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
 
         Expr::Symbol(s) => (ValueParticle::Symbol(*s), st),
-        Expr::Float(_) => todo!(),
-        Expr::Int(i) => (ValueParticle::Int(*i), st),
+        Expr::Float(x) => (ValueParticle::Float(*x), st),
+        Expr::Int(i) => match i.to_i64() {
+            Some(i) => (ValueParticle::Int(i), st),
+            // ints that don't fit into an i64 are not tracked precisely.
+            None => {
+                let value_id = ValueId(Symbol::new_fresh("intVID"));
+                st.deref.insert(value_id, ValueSet(vec![ValueParticle::TopInt]));
+                (ValueParticle::ValueId(value_id), st)
+            },
+        },
         Expr::Str(s) => (ValueParticle::String(s.clone()), st),
     }
 }
@@ -99,7 +180,7 @@ fn step_stmt(mut st: ThreadState, stmt: &Statement, ir: &IR) -> Vec<ThreadState>
             }
             outs
         }
-        Statement::Print(_) => vec![st],
-        Statement::Exit | Statement::Panic(_) | Statement::Fail => Vec::new(),
+        Statement::Print(_) | Statement::Eprint(_) | Statement::Write(_) => vec![st],
+        Statement::Exit(_) | Statement::Panic(_) | Statement::Fail => Vec::new(),
     }
 }
@@ -12,6 +12,8 @@ pub enum ValueParticle {
     TopString,
     Int(i64),
     TopInt,
+    Float(R64),
+    TopFloat,
     TableSort(TableSortId),
     ValueId(ValueId),
 }
@@ -48,6 +50,8 @@ impl ValueParticle {
             (ValueParticle::TopString, ValueParticle::String(_)) => true,
             (ValueParticle::Int(_), ValueParticle::TopInt) => true,
             (ValueParticle::TopInt, ValueParticle::Int(_)) => true,
+            (ValueParticle::Float(_), ValueParticle::TopFloat) => true,
+            (ValueParticle::TopFloat, ValueParticle::Float(_)) => true,
             (x, y) => x == y,
         }
     }
@@ -56,6 +60,7 @@ impl ValueParticle {
         matches!(self, ValueParticle::ValueId(_)
             | ValueParticle::String(_)
             | ValueParticle::Int(_)
+            | ValueParticle::Float(_)
             | ValueParticle::Symbol(_)
         )
     }
@@ -136,6 +141,7 @@ pub fn upcast(p: &ValueParticle, deref: &Deref) -> Option<ValueSet> {
         ValueParticle::ValueId(v) => deref.get(v).cloned(),
         ValueParticle::String(_) => Some(ValueSet(vec![ValueParticle::TopString])),
         ValueParticle::Int(_) => Some(ValueSet(vec![ValueParticle::TopInt])),
+        ValueParticle::Float(_) => Some(ValueSet(vec![ValueParticle::TopFloat])),
         ValueParticle::Top => None,
         _ => Some(ValueSet(vec![ValueParticle::Top])),
     }
@@ -11,20 +11,71 @@ pub fn step_binop(kind: BinOpKind, l: ValueSet, r: ValueSet, mut st: ProcState)
     let mut vs = ValueSet::bottom();
     for l in &l.0 {
         for r in &r.0 {
+            // int and float mixed, comparisons are exact. Otherwise the int gets converted.
+            if matches!(kind, Lt | Le | Gt | Ge) {
+                match (l, r) {
+                    (Int(l), Float(r)) => { vs.0.push(boolify(cmp_holds(kind, cmp_int_float(&(*l).into(), *r)))); continue },
+                    (Float(l), Int(r)) => { vs.0.push(boolify(cmp_holds(kind, cmp_int_float(&(*r).into(), *l).reverse()))); continue },
+                    _ => {},
+                }
+            }
+            let (l, r) = &match (l, r) {
+                (Int(l), Float(r)) => (fold_float(*l as f64), Float(*r)),
+                (Float(l), Int(r)) => (Float(*l), fold_float(*r as f64)),
+                (l, r) => (l.clone(), r.clone()),
+            };
             let out = match (kind, l, r) {
-                (Plus, Int(l), Int(r)) => vs.0.push(Int(l + r)),
-                (Minus, Int(l), Int(r)) => vs.0.push(Int(l - r)),
-                (Mul, Int(l), Int(r)) => vs.0.push(Int(l * r)),
-                (Div, Int(l), Int(r)) => vs.0.push(Int(l / r)),
-                (Mod, Int(l), Int(r)) => vs.0.push(Int(l % r)),
-                (Pow, Int(l), Int(r)) => vs.0.push(Int(l.pow(*r as _))),
+                // crashes exec, so there is no result.
+                (Div | Mod, _, Int(0)) => {},
+                (Div | Mod, _, Float(r)) if *r == 0.0 => {},
+
+                (Plus, Int(l), Int(r)) => vs.0.push(fold_int(l.checked_add(*r))),
+                (Minus, Int(l), Int(r)) => vs.0.push(fold_int(l.checked_sub(*r))),
+                (Mul, Int(l), Int(r)) => vs.0.push(fold_int(l.checked_mul(*r))),
+                (Div, Int(l), Int(r)) => vs.0.push(fold_int(py_floordiv(&(*l).into(), &(*r).into()).to_i64())),
+                (Mod, Int(l), Int(r)) => vs.0.push(fold_int(py_mod(&(*l).into(), &(*r).into()).to_i64())),
+                (Pow, Int(l), Int(r)) if *r < 0 => vs.0.push(fold_float((*l as f64).powf(*r as f64))),
+                (Pow, Int(l), Int(r)) => vs.0.push(fold_int(u32::try_from(*r).ok().and_then(|r| l.checked_pow(r)))),
+                // a negative shift count crashes exec.
+                (Shl | Shr, Int(_), Int(r)) if *r < 0 => {},
+                (Shl, Int(l), Int(r)) => vs.0.push(fold_int(py_shl(*l, *r))),
+                (Shr, Int(l), Int(r)) => vs.0.push(Int(py_shr(*l, *r))),
+                (BitAnd, Int(l), Int(r)) => vs.0.push(Int(l & r)),
+                (BitOr, Int(l), Int(r)) => vs.0.push(Int(l | r)),
+                (BitXor, Int(l), Int(r)) => vs.0.push(Int(l ^ r)),
                 (Lt, Int(l), Int(r)) => vs.0.push(boolify(l < r)),
                 (Le, Int(l), Int(r)) => vs.0.push(boolify(l <= r)),
                 (Gt, Int(l), Int(r)) => vs.0.push(boolify(l > r)),
                 (Ge, Int(l), Int(r)) => vs.0.push(boolify(l >= r)),
-                (kind, l, r) if l.overlaps(&TopInt) && r.overlaps(&TopInt) => {
+
+                (Plus, Float(l), Float(r)) => vs.0.push(fold_float(l.raw() + r.raw())),
+                (Minus, Float(l), Float(r)) => vs.0.push(fold_float(l.raw() - r.raw())),
+                (Mul, Float(l), Float(r)) => vs.0.push(fold_float(l.raw() * r.raw())),
+                (Div, Float(l), Float(r)) => vs.0.push(fold_float(l.raw() / r.raw())),
+                (Mod, Float(l), Float(r)) => vs.0.push(Float(py_fmod(*l, *r))),
+                (Pow, Float(l), Float(r)) => vs.0.push(fold_float(l.raw().powf(r.raw()))),
+                (Lt, Float(l), Float(r)) => vs.0.push(boolify(l < r)),
+                (Le, Float(l), Float(r)) => vs.0.push(boolify(l <= r)),
+                (Gt, Float(l), Float(r)) => vs.0.push(boolify(l > r)),
+                (Ge, Float(l), Float(r)) => vs.0.push(boolify(l >= r)),
+
+                (Plus, String(l), String(r)) => vs.0.push(String(format!("{l}{r}"))),
+                (Plus, l, r) if l.overlaps(&TopString) && r.overlaps(&TopString) => vs.0.push(TopString),
+                (Lt, String(l), String(r)) => vs.0.push(boolify(l < r)),
+                (Le, String(l), String(r)) => vs.0.push(boolify(l <= r)),
+                (Gt, String(l), String(r)) => vs.0.push(boolify(l > r)),
+                (Ge, String(l), String(r)) => vs.0.push(boolify(l >= r)),
+                (Lt|Le|Gt|Ge, l, r) if l.overlaps(&TopString) && r.overlaps(&TopString) => vs.0.extend([true_.clone(), false_.clone()]),
+
+                (kind, l, r) if is_num(l) && is_num(r) => {
                     let vs = if matches!(kind, Lt|Le|Gt|Ge) {
                         vs.0.extend([true_.clone(), false_.clone()]);
+                    } else if l.overlaps(&TopFloat) || r.overlaps(&TopFloat) {
+                        // floats that aren't finite are Undef.
+                        vs.0.extend([TopFloat, undef()]);
+                    } else if kind == Pow {
+                        // negative exponents give a float.
+                        vs.0.extend([TopInt, TopFloat, undef()]);
                     } else {
                         vs.0.push(TopInt);
                     };
@@ -36,3 +87,21 @@ pub fn step_binop(kind: BinOpKind, l: ValueSet, r: ValueSet, mut st: ProcState)
     (vs.compactify(), st)
 }
 
+
+fn is_num(x: &ValueParticle) -> bool {
+    x.overlaps(&ValueParticle::TopInt) || x.overlaps(&ValueParticle::TopFloat)
+}
+
+// results that don't fit into an i64 are not tracked precisely.
+pub fn fold_int(x: Option<i64>) -> ValueParticle {
+    x.map_or(ValueParticle::TopInt, ValueParticle::Int)
+}
+
+// floats that aren't finite are Undef, like in exec.
+pub fn fold_float(x: f64) -> ValueParticle {
+    finite(x).map_or_else(undef, ValueParticle::Float)
+}
+
+fn undef() -> ValueParticle {
+    ValueParticle::Symbol(crate::symbol::Symbol::new("Undef"))
+}
@@ -9,6 +9,8 @@ impl Debug for ValueParticle {
             ValueParticle::TopString => write!(f, "TopString"),
             ValueParticle::Int(i) => write!(f, "{i}"),
             ValueParticle::TopInt => write!(f, "TopInt"),
+            ValueParticle::Float(x) => write!(f, "{}", fmt_float_literal(*x)),
+            ValueParticle::TopFloat => write!(f, "TopFloat"),
             ValueParticle::Concrete(loc) => write!(f, "@{}", fmt_loc(*loc)),
             ValueParticle::Summary(loc) => write!(f, "*{}", fmt_loc(*loc)),
         }
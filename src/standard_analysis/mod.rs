@@ -16,7 +16,10 @@ pub use table::*;
 
 mod fmt;
 
-const HIST_LEN: usize = 5;
+// states are only kept apart by the last distinct procs, so the history has to reach back over the runtime procs
+// that run between two procs of the user program. Otherwise, the runtime would merge the states of all its callers.
+// On the etests, that stretch is at most 42 procs in 99% of the cases.
+const HIST_LEN: usize = 50;
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
 pub struct Hist(Vec<Symbol>);
@@ -77,10 +80,18 @@ impl ProcState {
             summarize_set(loc, vs);
         }
 
-        for ([mut t, mut k], mut v) in std::mem::take(&mut self.tables) {
-            summarize_set(loc, &mut v);
-            if t == ValueParticle::Concrete(loc) { t = ValueParticle::Summary(loc); }
-            if k == ValueParticle::Concrete(loc) { k = ValueParticle::Summary(loc); }
+        for (_, vs) in self.tables.iter_mut() {
+            summarize_set(loc, vs);
+        }
+
+        // only the entries of the concrete table, or with a concrete key need to be moved.
+        let c = ValueParticle::Concrete(loc);
+        let moved: Vec<[ValueParticle; 2]> = self.tables.keys().filter(|[t, k]| *t == c || *k == c).cloned().collect();
+        for tk in moved {
+            let v = self.tables.swap_remove(&tk).unwrap();
+            let [mut t, mut k] = tk;
+            if t == c { t = ValueParticle::Summary(loc); }
+            if k == c { k = ValueParticle::Summary(loc); }
             let vv = self.tables.entry([t, k]).or_default();
             *vv = vv.union(&v);
         }
@@ -94,9 +105,17 @@ impl ProcState {
         assert_eq!(self.pid, other.pid);
 
         let mut changed = false;
-        for ([t, k], v) in &other.tables {
-            let tk = [t.clone(), k.clone()];
-            let my_v = self.tables.entry(tk).or_default();
+        for (i, (tk, v)) in other.tables.iter().enumerate() {
+            // both states usually stem from the same state, so their entries tend to be in the same order.
+            let my_v = match self.tables.get_index_mut(i) {
+                Some((k, my_v)) if k == tk => Some(my_v),
+                _ => self.tables.get_mut(tk),
+            };
+            let Some(my_v) = my_v else {
+                changed |= !v.is_bottom();
+                self.tables.insert(tk.clone(), v.clone());
+                continue
+            };
             if !v.subseteq(my_v) {
                 *my_v = my_v.union(v);
                 changed = true;
@@ -107,6 +126,7 @@ impl ProcState {
 }
 
 fn summarize_set(loc: Location, vs: &mut ValueSet) {
+    if !vs.0.contains(&ValueParticle::Concrete(loc)) { return; }
     let n = vs.0.len();
     vs.0.retain(|x| *x != ValueParticle::Concrete(loc));
     if n != vs.0.len() && !vs.0.contains(&ValueParticle::Summary(loc)) {
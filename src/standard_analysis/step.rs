@@ -43,10 +43,102 @@ fn step_expr(mut st: ProcState, loc: Location, expr: &Expr) -> (ValueSet, ProcSt
             let vs = ValueSet(vec![ValueParticle::TopString]);
             (vs, st)
         },
+        Expr::Repr(n) => {
+            let mut vs = ValueSet::bottom();
+            for x in &st.nodes[n].0 {
+                match x {
+                    ValueParticle::Int(i) => vs.0.push(ValueParticle::String(i.to_string())),
+                    ValueParticle::Float(x) => vs.0.push(ValueParticle::String(py_float_repr(*x))),
+                    ValueParticle::String(s) => vs.0.push(ValueParticle::String(py_str_repr(s))),
+                    ValueParticle::TopInt | ValueParticle::TopFloat | ValueParticle::TopString => vs.0.push(ValueParticle::TopString),
+                    // everything else crashes exec.
+                    _ => {},
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::StrLen(n) => {
+            let mut vs = ValueSet::bottom();
+            for x in &st.nodes[n].0 {
+                match x {
+                    ValueParticle::String(s) => vs.0.push(ValueParticle::Int(s.chars().count() as i64)),
+                    ValueParticle::TopString => vs.0.push(ValueParticle::TopInt),
+                    _ => {},
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::Substr(s, i, j) => {
+            let mut vs = ValueSet::bottom();
+            for s in &st.nodes[s].0 {
+                for i in &st.nodes[i].0 {
+                    for j in &st.nodes[j].0 {
+                        match (s, i, j) {
+                            (ValueParticle::String(s), ValueParticle::Int(i), ValueParticle::Int(j)) => vs.0.push(ValueParticle::String(py_substr(s, &(*i).into(), &(*j).into()))),
+                            (s, i, j) if s.overlaps(&ValueParticle::TopString) && i.overlaps(&ValueParticle::TopInt) && j.overlaps(&ValueParticle::TopInt) => vs.0.push(ValueParticle::TopString),
+                            // everything else crashes exec.
+                            _ => {},
+                        }
+                    }
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::ParseInt(n) => {
+            let undef = ValueParticle::Symbol(Symbol::new("Undef"));
+            let mut vs = ValueSet::bottom();
+            for x in &st.nodes[n].0 {
+                match x {
+                    ValueParticle::String(s) => vs.0.push(py_parse_int(s).map_or(undef.clone(), |i| fold_int(i.to_i64()))),
+                    ValueParticle::TopString => vs.0.extend([ValueParticle::TopInt, undef.clone()]),
+                    _ => {},
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::ParseFloat(n) => {
+            let undef = ValueParticle::Symbol(Symbol::new("Undef"));
+            let mut vs = ValueSet::bottom();
+            for x in &st.nodes[n].0 {
+                match x {
+                    ValueParticle::String(s) => vs.0.push(py_parse_float(s).map_or(undef.clone(), ValueParticle::Float)),
+                    ValueParticle::TopString => vs.0.extend([ValueParticle::TopFloat, undef.clone()]),
+                    _ => {},
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::TrueDiv(l, r) => {
+            let undef = ValueParticle::Symbol(Symbol::new("Undef"));
+            let mut vs = ValueSet::bottom();
+            for l in &st.nodes[l].0 {
+                for r in &st.nodes[r].0 {
+                    match (l, r) {
+                        // crashes exec.
+                        (_, ValueParticle::Int(0)) => {},
+                        (ValueParticle::Int(l), ValueParticle::Int(r)) => vs.0.push(py_truediv(&(*l).into(), &(*r).into()).map_or(undef.clone(), ValueParticle::Float)),
+                        (l, r) if l.overlaps(&ValueParticle::TopInt) && r.overlaps(&ValueParticle::TopInt) => vs.0.extend([ValueParticle::TopFloat, undef.clone()]),
+                        _ => {},
+                    }
+                }
+            }
+            (vs.compactify(), st)
+        },
+        Expr::Trunc(n) => {
+            let mut vs = ValueSet::bottom();
+            for x in &st.nodes[n].0 {
+                match x {
+                    ValueParticle::Float(x) => vs.0.push(fold_int(py_trunc(*x).to_i64())),
+                    ValueParticle::TopFloat => vs.0.push(ValueParticle::TopInt),
+                    _ => {},
+                }
+            }
+            (vs.compactify(), st)
+        },
 
         Expr::Symbol(s) => (ValueSet(vec![ValueParticle::Symbol(*s)]), st),
-        Expr::Float(_) => todo!(),
-        Expr::Int(i) => (ValueSet(vec![ValueParticle::Int(*i)]), st),
+        Expr::Float(x) => (ValueSet(vec![ValueParticle::Float(*x)]), st),
+        Expr::Int(i) => (ValueSet(vec![fold_int(i.to_i64())]), st),
         Expr::Str(s) => (ValueSet(vec![ValueParticle::String(s.clone())]), st),
     }
 }
@@ -70,21 +162,25 @@ fn step_stmt(mut hist: Hist, mut st: ProcState, stmt: &Statement, loc: Location,
 
             st.nodes.clear();
 
-            let mut outs = Vec::new();
-            let procs = vs.0.iter().filter_map(|x| match x {
+            let mut procs: Vec<Symbol> = vs.0.iter().filter_map(|x| match x {
                 ValueParticle::Symbol(s) if ir.procs.contains_key(s) => Some(*s),
                 _ => None,
-            });
-            for pid in procs {
+            }).collect();
+
+            let successor = |pid: Symbol, mut st: ProcState| {
                 let mut hist = hist.clone();
-                let mut st = st.clone();
                 st.pid = pid;
                 hist.step(pid);
-                outs.push((hist, st));
-            }
+                (hist, st)
+            };
+
+            // the last successor can take the state itself, the others get a clone.
+            let Some(last) = procs.pop() else { return Vec::new() };
+            let mut outs: Vec<_> = procs.into_iter().map(|pid| successor(pid, st.clone())).collect();
+            outs.push(successor(last, st));
             outs
         }
-        Statement::Print(_) => vec![(hist, st)],
-        Statement::Exit | Statement::Panic(_) | Statement::Fail => Vec::new(),
+        Statement::Print(_) | Statement::Eprint(_) | Statement::Write(_) => vec![(hist, st)],
+        Statement::Exit(_) | Statement::Panic(_) | Statement::Fail => Vec::new(),
     }
 }
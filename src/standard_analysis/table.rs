@@ -23,6 +23,8 @@ pub fn tab_store(t: &ValueSet, k: &ValueSet, v: &ValueSet, st: &mut ProcState) {
                 store_impl(t, &ValueParticle::TopString, v, false, st);
             } else if let ValueParticle::Int(_) = k {
                 store_impl(t, &ValueParticle::TopInt, v, false, st);
+            } else if let ValueParticle::Float(_) = k {
+                store_impl(t, &ValueParticle::TopFloat, v, false, st);
             }
         }
     }
@@ -12,6 +12,8 @@ pub enum ValueParticle {
     TopString,
     Int(i64),
     TopInt,
+    Float(R64),
+    TopFloat,
     Summary(Location),
     Concrete(Location),
 }
@@ -21,6 +23,7 @@ impl ValueParticle {
         if other.0.contains(self) { return true; }
         if matches!(self, ValueParticle::String(_)) && other.0.contains(&ValueParticle::TopString) { return true; }
         if matches!(self, ValueParticle::Int(_)) && other.0.contains(&ValueParticle::TopInt) { return true; }
+        if matches!(self, ValueParticle::Float(_)) && other.0.contains(&ValueParticle::TopFloat) { return true; }
         false
     }
 
@@ -30,6 +33,8 @@ impl ValueParticle {
             (ValueParticle::TopString, ValueParticle::String(_)) => true,
             (ValueParticle::Int(_), ValueParticle::TopInt) => true,
             (ValueParticle::TopInt, ValueParticle::Int(_)) => true,
+            (ValueParticle::Float(_), ValueParticle::TopFloat) => true,
+            (ValueParticle::TopFloat, ValueParticle::Float(_)) => true,
             (x, y) => x == y,
         }
     }
@@ -38,6 +43,7 @@ impl ValueParticle {
         matches!(self, ValueParticle::Concrete(_)
             | ValueParticle::String(_)
             | ValueParticle::Int(_)
+            | ValueParticle::Float(_)
             | ValueParticle::Symbol(_)
         )
     }
@@ -53,14 +59,20 @@ impl ValueSet {
     pub fn compactify(mut self) -> Self {
         let top_str = self.0.contains(&ValueParticle::TopString);
         let top_int = self.0.contains(&ValueParticle::TopInt);
+        let top_float = self.0.contains(&ValueParticle::TopFloat);
         if top_str { self.0.retain(|x| !matches!(x, ValueParticle::String(_))); }
         if top_int { self.0.retain(|x| !matches!(x, ValueParticle::Int(_))); }
+        if top_float { self.0.retain(|x| !matches!(x, ValueParticle::Float(_))); }
         self.0.sort();
         self.0.dedup();
         if self.0.iter().filter(|x| matches!(x, ValueParticle::Int(_))).count() > 50 {
             self.0.retain(|x| !matches!(x, ValueParticle::Int(_)));
             self.0.push(ValueParticle::TopInt);
         }
+        if self.0.iter().filter(|x| matches!(x, ValueParticle::Float(_))).count() > 50 {
+            self.0.retain(|x| !matches!(x, ValueParticle::Float(_)));
+            self.0.push(ValueParticle::TopFloat);
+        }
         if self.0.iter().filter(|x| matches!(x, ValueParticle::String(_))).count() > 50 {
             self.0.retain(|x| !matches!(x, ValueParticle::String(_)));
             self.0.push(ValueParticle::TopString);
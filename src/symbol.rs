@@ -104,25 +104,17 @@ use std::cmp::*;
 
 impl PartialOrd for Symbol {
     fn partial_cmp(&self, other: &Symbol) -> Option<Ordering> {
-        let a = self;
-        let b = other;
-
-        let a = gsymb_get(*a);
-        let b = gsymb_get(*b);
-
-        for (ca, cb) in a.chars().zip(b.chars()) {
-            let o = ca.cmp(&cb);
-            if o != Ordering::Equal { return Some(o); }
-        }
-
-        let o = a.len().cmp(&b.len());
-        Some(o)
+        Some(self.cmp(other))
     }
 }
 
+// symbols are ordered by their strings.
 impl Ord for Symbol {
     fn cmp(&self, other: &Symbol) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        if self == other { return Ordering::Equal; }
+
+        let g = GSYMB.lock().unwrap();
+        g.get(*self).cmp(g.get(*other))
     }
 }
 